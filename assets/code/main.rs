@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+mod player;
+
+use player::PlayerPlugin;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "My Jam Game".to_string(),
+                resolution: (1280.0, 720.0).into(),
+                ..default()
+            }),
+            ..default()
+        }))
+        .add_state::<GameState>()
+        .add_plugins(PlayerPlugin)
+        .add_systems(Startup, spawn_camera)
+        .add_systems(OnEnter(GameState::Playing), spawn_level)
+        .add_systems(Update, restart.run_if(in_state(GameState::GameOver)))
+        .run();
+}
+
+#[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameState {
+    #[default]
+    Playing,
+    GameOver,
+}
+
+#[derive(Component)]
+pub struct Wall;
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+fn spawn_level(mut commands: Commands) {
+    // TODO: Load the level from a file
+    for i in -10..=10 {
+        for y in [-300.0, 300.0] {
+            commands.spawn((
+                Name::new("Wall"),
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgb(0.3, 0.3, 0.4),
+                        custom_size: Some(Vec2::splat(32.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(i as f32 * 32.0, y, 0.0),
+                    ..default()
+                },
+                Wall,
+            ));
+        }
+    }
+}
+
+fn restart(keys: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if keys.just_pressed(KeyCode::R) {
+        next_state.set(GameState::Playing);
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::Wall;
+
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), spawn_player)
+            .add_systems(OnExit(GameState::Playing), despawn_player)
+            .add_systems(
+                Update,
+                (move_player, collide_with_walls)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+const PLAYER_SPEED: f32 = 300.0;
+const PLAYER_SIZE: f32 = 24.0;
+
+#[derive(Component)]
+pub struct Player {
+    pub velocity: Vec2,
+}
+
+fn spawn_player(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Player"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(1.0, 0.5, 0.2),
+                custom_size: Some(Vec2::splat(PLAYER_SIZE)),
+                ..default()
+            },
+            ..default()
+        },
+        Player {
+            velocity: Vec2::ZERO,
+        },
+    ));
+}
+
+fn despawn_player(mut commands: Commands, player_query: Query<Entity, With<Player>>) {
+    for entity in &player_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn move_player(
+    time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
+    mut player_query: Query<(&mut Player, &mut Transform)>,
+) {
+    let mut input = Vec2::ZERO;
+    if keys.pressed(KeyCode::W) {
+        input.y += 1.0;
+    }
+    if keys.pressed(KeyCode::S) {
+        input.y -= 1.0;
+    }
+    if keys.pressed(KeyCode::A) {
+        input.x -= 1.0;
+    }
+    if keys.pressed(KeyCode::D) {
+        input.x += 1.0;
+    }
+
+    for (mut player, mut transform) in &mut player_query {
+        player.velocity = input.normalize_or_zero() * PLAYER_SPEED;
+        transform.translation += (player.velocity * time.delta_seconds()).extend(0.0);
+    }
+}
+
+fn collide_with_walls(
+    player_query: Query<&Transform, With<Player>>,
+    wall_query: Query<&Transform, (With<Wall>, Without<Player>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for player in &player_query {
+        let hit = wall_query.iter().any(|wall| {
+            let delta = (player.translation - wall.translation).truncate().abs();
+            delta.x < (PLAYER_SIZE + 32.0) / 2.0 && delta.y < (PLAYER_SIZE + 32.0) / 2.0
+        });
+        if hit {
+            // FIXME: Play a sound
+            next_state.set(GameState::GameOver);
+        }
+    }
+}
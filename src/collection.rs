@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::history::format_date;
+use crate::history::now_timestamp;
+use crate::simulation::AtlasList;
+use crate::simulation::GameMode;
+use crate::simulation::RunStats;
+use crate::simulation::Simulation;
+use crate::simulation::SpritePackEvent;
+use crate::simulation::TileKey;
+use crate::state::AppState;
+use crate::AppSet;
+
+pub struct CollectionPlugin;
+
+impl Plugin for CollectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SkinCollectionConfig>()
+            .register_type::<TileKey>()
+            .register_type::<SkinRecord>()
+            .register_type::<SkinCollection>()
+            .insert_resource(SkinCollection::load())
+            .init_resource::<RunSkins>()
+            .add_systems(
+                OnEnter(AppState::EditorScreen),
+                (start_collecting, collect_skins).chain(),
+            )
+            .add_systems(
+                Update,
+                collect_skins
+                    .in_set(AppSet::End)
+                    .run_if(in_state(AppState::EditorScreen))
+                    .run_if(on_event::<SpritePackEvent>().or_else(skin_count_changed)),
+            )
+            .add_systems(OnExit(AppState::EditorScreen), save_collection);
+    }
+}
+
+/// The file that the skin collection is saved to, one record per line.
+#[cfg(not(feature = "web"))]
+const COLLECTION_PATH: &str = "skin_collection.ron";
+
+/// The local storage key that the skin collection is saved to on web, one record per line.
+#[cfg(feature = "web")]
+const COLLECTION_KEY: &str = "skin_collection";
+
+#[derive(Default, Reflect, Serialize, Deserialize)]
+pub struct SkinCollectionConfig {
+    /// Presentation score added to each jam per completed sprite sheet.
+    atlas_bonus: f64,
+    /// Presentation score added to each jam per completed sprite pack.
+    pack_bonus: f64,
+}
+
+/// A skin discovered in a past run.
+#[derive(Reflect, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkinRecord {
+    pub tile: TileKey,
+    /// The number of runs that the skin was discovered in.
+    pub count: usize,
+    /// Seconds since the Unix epoch when the skin was first discovered.
+    pub first_seen: u64,
+}
+
+impl SkinRecord {
+    /// Formats the date of the first discovery as YYYY-MM-DD (UTC).
+    pub fn date(&self) -> String {
+        format_date(self.first_seen)
+    }
+}
+
+/// Every skin discovered across all runs, in the order they were first discovered.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct SkinCollection {
+    pub records: Vec<SkinRecord>,
+}
+
+impl SkinCollection {
+    fn load() -> Self {
+        #[cfg(feature = "web")]
+        let collection_str = crate::util::web::storage_get(COLLECTION_KEY);
+        #[cfg(not(feature = "web"))]
+        let collection_str = std::fs::read_to_string(COLLECTION_PATH).ok();
+        let Some(collection_str) = collection_str else {
+            return Self::default();
+        };
+
+        let records = collection_str
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                ron::from_str::<SkinRecord>(line)
+                    .map_err(|e| warn!("Skipping skin record: {e}"))
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        info!("Loaded {} collected skins", records.len());
+
+        Self { records }
+    }
+
+    /// Rewrites the collection file (or local storage on web) with every record.
+    fn save(&self) {
+        let result = self
+            .records
+            .iter()
+            .map(|record| ron::to_string(record).map(|line| line + "\n"))
+            .collect::<Result<String, _>>()
+            .map_err(|e| e.to_string())
+            .and_then(|lines| {
+                #[cfg(not(feature = "web"))]
+                let result = std::fs::write(COLLECTION_PATH, lines).map_err(|e| e.to_string());
+                #[cfg(feature = "web")]
+                let result = crate::util::web::storage_set(COLLECTION_KEY, &lines);
+                result
+            });
+        if let Err(e) = result {
+            error!("Saving skin collection: {e}");
+        }
+    }
+
+    /// Counts a discovery of the skin, adding it to the collection if it's new.
+    fn discover(&mut self, tile: TileKey, timestamp: u64) {
+        if let Some(record) = self.records.iter_mut().find(|record| record.tile == tile) {
+            record.count += 1;
+        } else {
+            self.records.push(SkinRecord {
+                tile,
+                count: 1,
+                first_seen: timestamp,
+            });
+        }
+    }
+
+    /// Returns the record of each discovered skin, by tile.
+    pub fn by_tile(&self) -> HashMap<&TileKey, &SkinRecord> {
+        self.records
+            .iter()
+            .map(|record| (&record.tile, record))
+            .collect()
+    }
+
+    /// Returns the number of completed sprite sheets and sprite packs.
+    pub fn completed_sets(&self, atlas_list: &AtlasList) -> (usize, usize) {
+        let by_tile = self.by_tile();
+        let is_complete = |atlas: usize| {
+            atlas_list
+                .tile_keys(atlas)
+                .all(|tile| by_tile.contains_key(&tile))
+        };
+
+        let mut atlases = 0;
+        let mut packs = 0;
+        // Every run starts with the skins of the "none" sprite pack, so it's not worth a bonus
+        for sprite_pack in atlas_list
+            .sprite_packs()
+            .filter(|&sprite_pack| !atlas_list.is_none_pack(sprite_pack))
+        {
+            let completed = atlas_list
+                .atlases(sprite_pack)
+                .filter(|&atlas| is_complete(atlas))
+                .count();
+            atlases += completed;
+            if completed == atlas_list.atlases(sprite_pack).len() {
+                packs += 1;
+            }
+        }
+
+        (atlases, packs)
+    }
+
+    /// Returns the Presentation score bonus for the completed sets.
+    pub fn presentation_bonus(&self, atlas_list: &AtlasList, config: &SkinCollectionConfig) -> f64 {
+        let (atlases, packs) = self.completed_sets(atlas_list);
+        atlases as f64 * config.atlas_bonus + packs as f64 * config.pack_bonus
+    }
+}
+
+/// The skins discovered during this run, so that each counts once per run.
+#[derive(Resource, Default)]
+struct RunSkins(HashSet<TileKey>);
+
+fn start_collecting(
+    config: Res<Config>,
+    game_mode: Res<GameMode>,
+    atlas_list: Res<AtlasList>,
+    collection: Res<SkinCollection>,
+    mut run_skins: ResMut<RunSkins>,
+    mut simulation: ResMut<Simulation>,
+    mut stats: ResMut<RunStats>,
+) {
+    run_skins.0.clear();
+
+    // Set bonuses only apply to regular jams, so daily challenges stay fair
+    if *game_mode == GameMode::Jam {
+        let bonus = collection.presentation_bonus(&atlas_list, &config.skin_collection);
+        simulation.presentation_score += bonus;
+        stats.collection_bonus = bonus;
+    }
+}
+
+/// Detects skins being added without a `SpritePackEvent`, e.g. by `SkinPlugin`.
+fn skin_count_changed(simulation: Res<Simulation>, mut count: Local<usize>) -> bool {
+    let changed = simulation.skin_set.skins.len() != *count;
+    *count = simulation.skin_set.skins.len();
+    changed
+}
+
+fn collect_skins(
+    game_mode: Res<GameMode>,
+    simulation: Res<Simulation>,
+    atlas_list: Res<AtlasList>,
+    mut collection: ResMut<SkinCollection>,
+    mut run_skins: ResMut<RunSkins>,
+) {
+    // Any skin can be picked in the sandbox, so it doesn't count
+    if *game_mode == GameMode::Sandbox {
+        return;
+    }
+
+    let timestamp = now_timestamp();
+    for skin in &simulation.skin_set.skins {
+        let tile = atlas_list.tile_key(skin);
+        if run_skins.0.insert(tile.clone()) {
+            collection.discover(tile, timestamp);
+        }
+    }
+}
+
+/// Saves the skins discovered during the run once it ends.
+fn save_collection(collection: Res<SkinCollection>, run_skins: Res<RunSkins>) {
+    if !run_skins.0.is_empty() {
+        collection.save();
+    }
+}
@@ -8,11 +8,18 @@ use serde::Deserialize;
 use serde::Serialize;
 use tap::TapFallible;
 
+use crate::collection::SkinCollectionConfig;
+use crate::simulation::CompilerErrorConfig;
+use crate::simulation::PaletteConfig;
+use crate::simulation::ScoringConfig;
+use crate::state::collection_screen::CollectionScreenConfig;
 use crate::state::editor_screen::EditorScreenConfig;
 use crate::state::loading_screen::LoadingScreenConfig;
+use crate::state::past_jams_screen::PastJamsScreenConfig;
 use crate::state::results_screen::ResultsScreenConfig;
 use crate::state::splash_screen::SplashScreenConfig;
 use crate::state::title_screen::TitleScreenConfig;
+use crate::ui::CodeTyperConfig;
 use crate::ui::TooltipConfig;
 use crate::AppRoot;
 
@@ -26,9 +33,10 @@ impl Plugin for ConfigPlugin {
         let config_str = &std::fs::read_to_string("assets/config.ron")
             .tap_err(|e| error!("Reading config: {e}"))
             .unwrap_or_default();
-        let config = from_str::<Config>(config_str)
+        let mut config = from_str::<Config>(config_str)
             .tap_err(|e| error!("Deserializing config: {e}"))
             .unwrap();
+        config.scoring.validate();
         info!("Loaded config");
 
         app.register_type::<Config>()
@@ -66,6 +74,13 @@ pub struct Config {
 
     // UI
     pub tooltip: TooltipConfig,
+    pub code_typer: CodeTyperConfig,
+
+    // Simulation
+    pub scoring: ScoringConfig,
+    pub compiler_error: CompilerErrorConfig,
+    pub palette: PaletteConfig,
+    pub skin_collection: SkinCollectionConfig,
 
     // App states
     pub splash_screen: SplashScreenConfig,
@@ -73,6 +88,8 @@ pub struct Config {
     pub loading_screen: LoadingScreenConfig,
     pub editor_screen: EditorScreenConfig,
     pub results_screen: ResultsScreenConfig,
+    pub past_jams_screen: PastJamsScreenConfig,
+    pub collection_screen: CollectionScreenConfig,
 }
 
 fn apply_config(config: Res<Config>, mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
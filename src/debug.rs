@@ -12,6 +12,8 @@ use bevy_editor_pls::EditorPlugin;
 use iyes_progress::prelude::*;
 use strum::IntoEnumIterator;
 
+use crate::simulation::GameTime;
+use crate::simulation::IncomeSource;
 use crate::simulation::LinesAddedEvent;
 use crate::simulation::Simulation;
 use crate::state::AppState;
@@ -130,12 +132,15 @@ impl Plugin for DebugPlugin {
             app.add_systems(
                 Update,
                 |mut cheat_settings: ResMut<CheatSettings>,
-                 time: Res<Time>,
+                 time: Res<GameTime>,
                  mut events: EventWriter<LinesAddedEvent>| {
                     if cheat_settings.generate_lines
                         && cheat_settings.timer.tick(time.delta()).just_finished()
                     {
-                        events.send(LinesAddedEvent { count: 1.0 });
+                        events.send(LinesAddedEvent {
+                            count: 1.0,
+                            source: IncomeSource::Cheat,
+                        });
                     }
                 },
             );
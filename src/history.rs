@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::simulation::JamTheme;
+
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<JamRecord>()
+            .register_type::<JamHistory>()
+            .insert_resource(JamHistory::load());
+    }
+}
+
+/// The file that past jams are appended to, one record per line.
+#[cfg(not(feature = "web"))]
+const HISTORY_PATH: &str = "jam_history.ron";
+
+/// The local storage key that past jams are appended to on web, one record per line.
+#[cfg(feature = "web")]
+const HISTORY_KEY: &str = "jam_history";
+
+/// The outcome of a single submitted jam entry.
+#[derive(Reflect, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JamRecord {
+    /// Seconds since the Unix epoch when the entry was submitted.
+    pub timestamp: u64,
+    pub theme: JamTheme,
+    /// Seconds of game time spent in the editor.
+    pub duration: f64,
+    /// The mean vote for each category, as in `Ratings::scores`.
+    pub scores: [f64; 4],
+    /// The Overall rank among all entries.
+    pub rank: usize,
+    pub entries: usize,
+    pub lines: f64,
+    pub total_lines: f64,
+    pub entities: f64,
+    pub tech_debt: f64,
+    pub fun_score: f64,
+    pub presentation_score: f64,
+    /// The name and count of each installed upgrade.
+    pub upgrades: Vec<(String, usize)>,
+    /// The RNG seed of the run, if one was used.
+    pub seed: Option<u64>,
+}
+
+impl JamRecord {
+    /// Formats the submission date as YYYY-MM-DD (UTC).
+    pub fn date(&self) -> String {
+        format_date(self.timestamp)
+    }
+}
+
+/// Formats a timestamp (in seconds since the Unix epoch) as YYYY-MM-DD (UTC).
+pub fn format_date(timestamp: u64) -> String {
+    // See: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = (timestamp / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+#[cfg(not(feature = "web"))]
+pub fn now_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// `SystemTime` is not available on web, so this asks the browser instead.
+#[cfg(feature = "web")]
+pub fn now_timestamp() -> u64 {
+    (crate::util::web::now_millis() / 1000.0) as u64
+}
+
+/// Every past jam, in the order they were submitted.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct JamHistory {
+    pub records: Vec<JamRecord>,
+}
+
+impl JamHistory {
+    fn load() -> Self {
+        #[cfg(feature = "web")]
+        let history_str = crate::util::web::storage_get(HISTORY_KEY);
+        #[cfg(not(feature = "web"))]
+        let history_str = std::fs::read_to_string(HISTORY_PATH).ok();
+        let Some(history_str) = history_str else {
+            return Self::default();
+        };
+
+        let records = history_str
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                ron::from_str::<JamRecord>(line)
+                    .map_err(|e| warn!("Skipping jam record: {e}"))
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        info!("Loaded {} past jams", records.len());
+
+        Self { records }
+    }
+
+    /// Adds a record to the history and appends it to the history file (or local storage on web).
+    pub fn push(&mut self, record: JamRecord) {
+        #[cfg(not(feature = "web"))]
+        {
+            use std::io::Write;
+
+            let result = ron::to_string(&record)
+                .map_err(|e| e.to_string())
+                .and_then(|line| {
+                    std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(HISTORY_PATH)
+                        .and_then(|mut file| writeln!(file, "{line}"))
+                        .map_err(|e| e.to_string())
+                });
+            if let Err(e) = result {
+                error!("Saving jam record: {e}");
+            }
+        }
+        #[cfg(feature = "web")]
+        {
+            use crate::util::web;
+
+            let result = ron::to_string(&record)
+                .map_err(|e| e.to_string())
+                .and_then(|line| {
+                    let history_str = web::storage_get(HISTORY_KEY).unwrap_or_default();
+                    web::storage_set(HISTORY_KEY, &format!("{history_str}{line}\n"))
+                });
+            if let Err(e) = result {
+                error!("Saving jam record: {e}");
+            }
+        }
+
+        self.records.push(record);
+    }
+
+    /// Returns the best score for each category, or None if there are no records.
+    pub fn best_scores(&self) -> Option<[f64; 4]> {
+        let first = self.records.first()?;
+        Some(self.records.iter().fold(first.scores, |mut best, record| {
+            for (best, &score) in best.iter_mut().zip(&record.scores) {
+                *best = best.max(score);
+            }
+            best
+        }))
+    }
+
+    /// Returns the best Overall score of the runs with the given seed, or None if there are none.
+    pub fn best_seeded_score(&self, seed: u64) -> Option<f64> {
+        self.records
+            .iter()
+            .filter(|record| record.seed == Some(seed))
+            .map(|record| record.scores[3])
+            .max_by(f64::total_cmp)
+    }
+
+    /// Returns the best Overall rank, or None if there are no records.
+    pub fn best_rank(&self) -> Option<usize> {
+        self.records.iter().map(|record| record.rank).min()
+    }
+}
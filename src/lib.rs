@@ -5,9 +5,11 @@
 
 mod audio;
 mod camera;
+mod collection;
 mod config;
 #[cfg(feature = "dev")]
 mod debug;
+mod history;
 mod physics;
 mod simulation;
 mod state;
@@ -62,6 +64,8 @@ impl Plugin for AppPlugin {
         // Other plugins
         app.add_plugins((
             camera::CameraPlugin,
+            collection::CollectionPlugin,
+            history::HistoryPlugin,
             simulation::SimulationPlugin,
             physics::PhysicsPlugin,
             ui::UiPlugin,
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+use crate::simulation::GameTime;
 use crate::AppRoot;
 use crate::AppSet;
 
@@ -32,7 +33,7 @@ pub struct PhysicsSettings {
 pub struct Velocity(pub Vec3);
 
 fn apply_velocity(
-    time: Res<Time>,
+    time: Res<GameTime>,
     physics: Res<PhysicsSettings>,
     mut velocity_query: Query<(&mut Transform, &Velocity)>,
 ) {
@@ -46,7 +47,7 @@ fn apply_velocity(
 }
 
 fn apply_mouse_force(
-    time: Res<Time>,
+    time: Res<GameTime>,
     physics: Res<PhysicsSettings>,
     root: Res<AppRoot>,
     window_query: Query<&Window>,
@@ -1,4 +1,11 @@
+mod challenge;
+mod compiler_error;
+mod game_mode;
+mod game_time;
+mod jam_theme;
+mod run_stats;
 mod sprite_pack;
+mod typing_speed;
 
 use std::f32::consts::TAU;
 
@@ -9,11 +16,28 @@ use rand::Rng;
 use rand::SeedableRng;
 
 use crate::physics::Velocity;
+pub use crate::simulation::challenge::DailyChallenge;
+pub use crate::simulation::challenge::Modifier;
+pub use crate::simulation::compiler_error::CompilerErrorConfig;
+pub use crate::simulation::compiler_error::CompilerErrors;
+pub use crate::simulation::game_mode::GameMode;
+pub use crate::simulation::game_time::GameSpeed;
+pub use crate::simulation::game_time::GameTime;
+pub use crate::simulation::jam_theme::JamTheme;
+pub use crate::simulation::run_stats::IncomeSource;
+pub use crate::simulation::run_stats::RunStats;
+pub use crate::simulation::run_stats::RunStatsSample;
+pub use crate::simulation::score::ScoringConfig;
+pub use crate::simulation::score::StarVotes;
 pub use crate::simulation::sprite_pack::AtlasList;
+pub use crate::simulation::sprite_pack::PaletteConfig;
 pub use crate::simulation::sprite_pack::SkinSet;
-pub use crate::simulation::sprite_pack::SpritePack;
+use crate::simulation::sprite_pack::SpriteAnimation;
 pub use crate::simulation::sprite_pack::SpritePackAssets;
 pub use crate::simulation::sprite_pack::SpritePackEvent;
+pub use crate::simulation::sprite_pack::TileKey;
+pub use crate::simulation::typing_speed::TypingSpeed;
+pub use crate::simulation::typing_speed::BASE_WPM;
 use crate::spawn_logical_entities;
 use crate::state::editor_screen::SceneViewBounds;
 use crate::state::editor_screen::WrapWithinSceneView;
@@ -30,15 +54,32 @@ impl Plugin for SimulationPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<SpawnEvent>()
             .register_type::<EntityPool>()
-            .add_plugins(sprite_pack::SpritePackPlugin)
+            .add_plugins((
+                challenge::ChallengePlugin,
+                compiler_error::CompilerErrorPlugin,
+                game_mode::GameModePlugin,
+                game_time::GameTimePlugin,
+                jam_theme::JamThemePlugin,
+                run_stats::RunStatsPlugin,
+                sprite_pack::SpritePackPlugin,
+                typing_speed::TypingSpeedPlugin,
+            ))
             .add_event::<SpawnEvent>()
             .add_event::<LinesAddedEvent>()
             .init_resource::<EntityPool>()
             .init_resource::<Simulation>()
+            .init_resource::<RunRng>()
             .init_resource::<PassiveCodeTyper>()
             .init_resource::<PassiveEntitySpawner>()
             .add_systems(Startup, spawn_entity_pool.after(spawn_logical_entities))
             .add_systems(OnExit(AppState::ResultsScreen), reset_entity_pool)
+            .add_systems(
+                OnTransition {
+                    from: AppState::EditorScreen,
+                    to: AppState::TitleScreen,
+                },
+                reset_entity_pool,
+            )
             .add_systems(
                 Update,
                 (
@@ -72,6 +113,12 @@ pub struct Simulation {
 
     /// Newly added line count will be multiplied by this.
     pub line_multiplier: f64,
+    /// Characters typed into the focused code tab per key press.
+    pub chars_per_key: usize,
+    /// If true, lines typed by the player will be multiplied by `TypingSpeed::bonus`.
+    pub typing_speed_bonus: bool,
+    /// The rate of compiler errors will be multiplied by this.
+    pub compiler_error_multiplier: f64,
 
     /// For each added line, this many entities will spawn.
     pub entity_spawn_per_line: f64,
@@ -82,15 +129,20 @@ pub struct Simulation {
     pub entity_size_min: f32,
     /// Maximum size for new entities.
     pub entity_size_max: f32,
-    /// List of colors that the new entities can have.
+    /// The color palette for randomly-tinted skins, or any color if empty.
     pub entity_colors: Vec<Color>,
     /// The set of entity skins to choose from.
     pub skin_set: SkinSet,
+    /// If true, entity skins with multiple frames will be animated.
+    pub animated_sprites: bool,
 
     /// Minimum offset distance for entities on spawn.
     pub spawn_offset_min: f32,
     /// Maximum offset distance for entities on spawn.
     pub spawn_offset_max: f32,
+
+    /// The challenge modifiers active during this run.
+    pub modifiers: Vec<Modifier>,
 }
 
 impl Default for Simulation {
@@ -107,20 +159,64 @@ impl Default for Simulation {
             total_lines: 0.0,
 
             line_multiplier: 1.0,
+            chars_per_key: 1,
+            typing_speed_bonus: false,
+            compiler_error_multiplier: 1.0,
 
             entity_spawn_per_line: 0.0,
             entity_spawn_multiplier: 1.0,
 
             entity_size_min: 8.0,
             entity_size_max: 8.0,
-            entity_colors: vec![
-                Color::rgba(0.0, 0.0, 0.0, 1.0),
-                Color::rgba(1.0, 1.0, 1.0, 1.0),
-            ],
+            entity_colors: vec![],
             skin_set: default(),
+            animated_sprites: false,
 
             spawn_offset_min: 0.0,
             spawn_offset_max: 2.0,
+
+            modifiers: vec![],
+        }
+    }
+}
+
+/// Random number generators for a run, so that a seeded run can be replayed.
+#[derive(Resource)]
+pub struct RunRng {
+    /// The seed of the run, or None if it was seeded from entropy.
+    pub seed: Option<u64>,
+    /// Picks the upgrades offered.
+    pub upgrades: SmallRng,
+    /// Places spawned entities.
+    pub entities: SmallRng,
+    /// Picks entity skins.
+    pub skins: SmallRng,
+    /// Picks the compiler errors triggered.
+    pub compiler_errors: SmallRng,
+}
+
+impl Default for RunRng {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            upgrades: SmallRng::from_entropy(),
+            entities: SmallRng::from_entropy(),
+            skins: SmallRng::from_entropy(),
+            compiler_errors: SmallRng::from_entropy(),
+        }
+    }
+}
+
+impl RunRng {
+    pub fn from_seed(seed: u64) -> Self {
+        // Use a separate stream for each purpose so that e.g. clicking more doesn't change
+        // the upgrades offered
+        Self {
+            seed: Some(seed),
+            upgrades: SmallRng::seed_from_u64(seed),
+            entities: SmallRng::seed_from_u64(seed ^ 0x656E74697469657),
+            skins: SmallRng::seed_from_u64(seed ^ 0x736B696E73),
+            compiler_errors: SmallRng::seed_from_u64(seed ^ 0x6572726F7273),
         }
     }
 }
@@ -158,26 +254,25 @@ impl EntityPool {
 fn spawn_entity_pool(world: &mut World) {
     world.resource_scope(|world: &mut World, mut pool: Mut<EntityPool>| {
         let capacity = pool.entities.capacity() - pool.entities.len();
-        pool.entities.extend(
-            world.spawn_batch(
-                std::iter::repeat((
-                    Name::new("Entity"),
-                    // NOTE: Workaround for SpatialBundle not impling Clone
-                    (
-                        Visibility::Hidden,
-                        InheritedVisibility::default(),
-                        ViewVisibility::default(),
-                        Transform::default(),
-                        GlobalTransform::default(),
-                    ),
-                    WrapWithinSceneView,
-                    Velocity::default(),
-                    TextureAtlasSprite::default(),
-                    Handle::<TextureAtlas>::default(),
-                ))
-                .take(capacity),
+        pool.entities.extend(world.spawn_batch(std::iter::repeat_n(
+            (
+                Name::new("Entity"),
+                // NOTE: Workaround for SpatialBundle not impling Clone
+                (
+                    Visibility::Hidden,
+                    InheritedVisibility::default(),
+                    ViewVisibility::default(),
+                    Transform::default(),
+                    GlobalTransform::default(),
+                ),
+                WrapWithinSceneView,
+                Velocity::default(),
+                TextureAtlasSprite::default(),
+                Handle::<TextureAtlas>::default(),
+                SpriteAnimation::default(),
             ),
-        );
+            capacity,
+        )));
 
         let parent = world.resource::<AppRoot>().world;
         for &entity in &pool.entities {
@@ -207,17 +302,31 @@ pub struct SpawnEvent {
     pub position: Vec2,
     pub count: f64,
     pub custom_cap: Option<usize>,
+    pub source: IncomeSource,
 }
 
 fn spawn_entities(world: &mut World, mut reader: Local<ManualEventReader<SpawnEvent>>) {
-    let mut rng = SmallRng::from_entropy();
+    world.resource_scope(|world: &mut World, mut rng: Mut<RunRng>| {
+        spawn_entities_with_rng(world, &mut reader, &mut rng);
+    });
+}
+
+fn spawn_entities_with_rng(
+    world: &mut World,
+    reader: &mut ManualEventReader<SpawnEvent>,
+    rng: &mut RunRng,
+) {
     for event in reader
         .read(world.resource::<Events<_>>())
         .copied()
         .collect::<Vec<_>>()
     {
         let mut simulation = world.resource_mut::<Simulation>();
-        simulation.entities += event.count * simulation.entity_spawn_multiplier;
+        let count = event.count * simulation.entity_spawn_multiplier;
+        simulation.entities += count;
+        world
+            .resource_mut::<RunStats>()
+            .add_entities(event.source, count);
 
         let simulation = world.resource::<Simulation>();
         let mut spawn_count = event.count as usize;
@@ -230,24 +339,29 @@ fn spawn_entities(world: &mut World, mut reader: Local<ManualEventReader<SpawnEv
 
         let mut bundles = vec![];
         for _ in 0..spawn_count {
-            let angle = rng.gen_range(0.0..=TAU);
+            let angle = rng.entities.gen_range(0.0..=TAU);
             let direction = Vec2::from_angle(angle);
 
-            let speed = rng.gen_range(0.5..=1.5);
+            let speed = rng.entities.gen_range(0.5..=1.5);
             let velocity = (speed * direction).extend(-0.01);
 
-            let offset = rng.gen_range(simulation.spawn_offset_min..=simulation.spawn_offset_max)
+            let offset = rng
+                .entities
+                .gen_range(simulation.spawn_offset_min..=simulation.spawn_offset_max)
                 * direction;
             let position = (event.position + offset).extend(0.0);
             let transform = Transform::from_translation(position);
 
-            let size = rng.gen_range(simulation.entity_size_min..=simulation.entity_size_max);
+            let size = rng
+                .entities
+                .gen_range(simulation.entity_size_min..=simulation.entity_size_max);
             let size = Vec2::splat(size);
 
-            let (sprite, texture) =
-                simulation
-                    .skin_set
-                    .bundle(world.resource::<SpritePackAssets>(), size, &mut rng);
+            let (sprite, texture, animation) = simulation.skin_set.bundle(
+                world.resource::<SpritePackAssets>(),
+                size,
+                &mut rng.skins,
+            );
 
             bundles.push((
                 Visibility::Inherited,
@@ -255,10 +369,11 @@ fn spawn_entities(world: &mut World, mut reader: Local<ManualEventReader<SpawnEv
                 Velocity(velocity),
                 sprite,
                 texture,
+                animation,
             ));
         }
 
-        for (visibility, transform, velocity, sprite, texture) in bundles {
+        for (visibility, transform, velocity, sprite, texture, animation) in bundles {
             let entity = world.resource_mut::<EntityPool>().recycle();
             let mut entity = world.entity_mut(entity);
             *entity.get_mut::<Visibility>().unwrap() = visibility;
@@ -266,6 +381,7 @@ fn spawn_entities(world: &mut World, mut reader: Local<ManualEventReader<SpawnEv
             *entity.get_mut::<Velocity>().unwrap() = velocity;
             *entity.get_mut::<TextureAtlasSprite>().unwrap() = sprite;
             *entity.get_mut::<Handle<TextureAtlas>>().unwrap() = texture;
+            *entity.get_mut::<SpriteAnimation>().unwrap() = animation;
         }
     }
 }
@@ -298,40 +414,71 @@ impl Default for PassiveCodeTyper {
     }
 }
 
+impl PassiveCodeTyper {
+    /// Splits characters into the count to enter into a code typer and the lines from the
+    /// overflow, so that huge counts don't stall the frame.
+    pub fn split_overflow(&self, chars: f64) -> (usize, f64) {
+        let count = chars.min(self.max_chars_entered);
+        (
+            count as usize,
+            (chars - count) / self.overflow_chars_per_line,
+        )
+    }
+}
+
 /// System for handling passive code generation.
 fn type_code_passively(
-    time: Res<Time>,
+    time: Res<GameTime>,
     mut typer: ResMut<PassiveCodeTyper>,
     simulation: Res<Simulation>,
+    errors: Res<CompilerErrors>,
     mut events: EventWriter<LinesAddedEvent>,
     mut code_query: Query<(&mut CodeTyper, &mut Text)>,
 ) {
-    let mut chars = 0.0;
+    // Compiler errors block passive typing until they're resolved
+    if errors.active.is_some() {
+        return;
+    }
+
+    let mut passive_chars = 0.0;
     if typer.timer.tick(time.delta()).just_finished() {
         typer.timer.reset();
-        chars += typer.chars;
+        passive_chars += typer.chars;
     }
 
+    let mut llm_chars = 0.0;
     if typer.llm_timer.tick(time.delta()).just_finished() {
         typer.llm_timer.reset();
-        chars += typer.chars_per_entity * simulation.entities;
+        llm_chars += typer.chars_per_entity * simulation.entities;
     }
 
+    let chars = passive_chars + llm_chars;
     if chars == 0.0 {
         return;
     }
 
-    let count = chars.min(typer.max_chars_entered);
-    let overflow = chars - count;
-    let mut new_lines = overflow / typer.overflow_chars_per_line;
+    let (count, mut new_lines) = typer.split_overflow(chars);
 
-    let count = count as usize;
-    for (mut code, mut text) in &mut code_query {
-        let lines = code.enter(&mut text.sections[0].value, count);
+    // Distribute the characters across the code tabs
+    let tabs = code_query.iter().len().max(1);
+    for (i, (mut code, mut text)) in code_query.iter_mut().enumerate() {
+        let count = count / tabs + usize::from(i < count % tabs);
+        let lines = code.enter(&mut text.sections, count);
         new_lines += lines;
     }
 
-    events.send(LinesAddedEvent { count: new_lines });
+    // Split the new lines between the sources by how many characters each typed
+    let passive_lines = new_lines * passive_chars / chars;
+    events.send_batch([
+        LinesAddedEvent {
+            count: passive_lines,
+            source: IncomeSource::PassiveTyper,
+        },
+        LinesAddedEvent {
+            count: new_lines - passive_lines,
+            source: IncomeSource::Llm,
+        },
+    ]);
 }
 
 /// Resource for handling passive entity spawning.
@@ -352,7 +499,7 @@ impl Default for PassiveEntitySpawner {
 
 /// System for handling passive entity spawning.
 fn spawn_entities_passively(
-    time: Res<Time>,
+    time: Res<GameTime>,
     mut spawner: ResMut<PassiveEntitySpawner>,
     mut events: EventWriter<SpawnEvent>,
     bounds: Res<SceneViewBounds>,
@@ -366,25 +513,29 @@ fn spawn_entities_passively(
         position: (bounds.min.xy() + bounds.max.xy()) / 2.0,
         count: spawner.amount,
         custom_cap: None,
+        source: IncomeSource::EntitySpawner,
     });
 }
 
 #[derive(Event, Reflect)]
 pub struct LinesAddedEvent {
     pub count: f64,
+    pub source: IncomeSource,
 }
 
 fn handle_line_added_events(
     mut events: EventReader<LinesAddedEvent>,
     mut simulation: ResMut<Simulation>,
+    mut stats: ResMut<RunStats>,
     mut spawn_events: EventWriter<SpawnEvent>,
     bounds: Res<SceneViewBounds>,
 ) {
     let mut total: f64 = 0.0;
     for event in events.read() {
-        total += event.count;
+        let count = event.count * simulation.line_multiplier;
+        stats.add_lines(event.source, count);
+        total += count;
     }
-    total *= simulation.line_multiplier;
     simulation.lines += total;
     simulation.total_lines += total;
 
@@ -395,6 +546,7 @@ fn handle_line_added_events(
             position: (bounds.min.xy() + bounds.max.xy()) / 2.0,
             count: spawned_entities,
             custom_cap: None,
+            source: IncomeSource::LineSpawner,
         });
     }
 }
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use rand::rngs::SmallRng;
+use rand::seq::IteratorRandom;
+use rand::SeedableRng;
+use serde::Deserialize;
+use serde::Serialize;
+use strum::EnumIter;
+use strum::IntoEnumIterator;
+
+use crate::history::format_date;
+use crate::history::now_timestamp;
+use crate::simulation::GameMode;
+use crate::simulation::JamTheme;
+use crate::simulation::RunRng;
+use crate::simulation::Simulation;
+use crate::state::AppState;
+use crate::upgrade::UpgradeKind;
+
+pub struct ChallengePlugin;
+
+impl Plugin for ChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<DailyChallenge>()
+            .init_resource::<DailyChallenge>()
+            .add_systems(OnEnter(AppState::EditorScreen), start_run);
+    }
+}
+
+/// A rule that makes a run harder.
+#[derive(Reflect, Copy, Clone, PartialEq, Eq, Debug, EnumIter, Serialize, Deserialize)]
+pub enum Modifier {
+    /// Clicking the scene view doesn't spawn entities.
+    NoClicking,
+    /// Technical debt raises upgrade costs twice as much.
+    DoubleTechDebt,
+    /// Unicorn Dev is the only specialization offered.
+    UnicornDevOnly,
+}
+
+impl Modifier {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::NoClicking => "No clicking",
+            Self::DoubleTechDebt => "Tech debt costs double",
+            Self::UnicornDevOnly => "Unicorn Dev only",
+        }
+    }
+
+    fn allows_upgrade(self, kind: UpgradeKind) -> bool {
+        use UpgradeKind::*;
+
+        match self {
+            Self::NoClicking => !matches!(
+                kind,
+                TouchOfLifePlugin | LaptopSticker | Coffee | ProgrammingSock | StandingDesk,
+            ),
+            Self::DoubleTechDebt => true,
+            Self::UnicornDevOnly => !matches!(kind, TenXDev | RockstarDev),
+        }
+    }
+}
+
+impl Simulation {
+    /// Returns false if an active modifier forbids the given upgrade from being offered.
+    pub fn allows_upgrade(&self, kind: UpgradeKind) -> bool {
+        self.modifiers
+            .iter()
+            .all(|modifier| modifier.allows_upgrade(kind))
+    }
+
+    pub fn allows_clicking(&self) -> bool {
+        !self.modifiers.contains(&Modifier::NoClicking)
+    }
+
+    /// The amount of technical debt that upgrade costs are scaled by.
+    pub fn effective_tech_debt(&self) -> f64 {
+        let scale = if self.modifiers.contains(&Modifier::DoubleTechDebt) {
+            2.0
+        } else {
+            1.0
+        };
+        self.tech_debt * self.tech_debt_multiplier * scale
+    }
+}
+
+/// The number of modifiers in each daily challenge.
+const DAILY_MODIFIERS: usize = 2;
+
+/// A challenge that is the same for everyone on the same (UTC) day.
+#[derive(Resource, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct DailyChallenge {
+    /// Days since the Unix epoch.
+    pub day: u64,
+    /// The RNG seed of the run.
+    pub seed: u64,
+    pub theme: JamTheme,
+    pub modifiers: Vec<Modifier>,
+}
+
+impl Default for DailyChallenge {
+    fn default() -> Self {
+        Self::today()
+    }
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        Self::for_day(now_timestamp() / 86400)
+    }
+
+    pub fn for_day(day: u64) -> Self {
+        // Spread consecutive days across the seed space (splitmix64)
+        let mut seed = day.wrapping_add(0x9E3779B97F4A7C15);
+        seed = (seed ^ (seed >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        seed = (seed ^ (seed >> 27)).wrapping_mul(0x94D049BB133111EB);
+        seed ^= seed >> 31;
+
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let theme = JamTheme::iter().choose(&mut rng).unwrap_or_default();
+        let modifiers = Modifier::iter().choose_multiple(&mut rng, DAILY_MODIFIERS);
+
+        Self {
+            day,
+            seed,
+            theme,
+            modifiers,
+        }
+    }
+
+    /// Formats the day as YYYY-MM-DD (UTC).
+    pub fn date(&self) -> String {
+        format_date(self.day * 86400)
+    }
+}
+
+impl GameMode {
+    /// Returns the theme of the run: the daily challenge's theme, or else the theme the player
+    /// chose on the title screen.
+    pub fn theme(self, chosen: JamTheme, challenge: &DailyChallenge) -> JamTheme {
+        match self {
+            Self::DailyChallenge => challenge.theme,
+            Self::Jam | Self::Sandbox => chosen,
+        }
+    }
+}
+
+/// Seeds the run and applies the modifiers of the daily challenge.
+fn start_run(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    challenge: Res<DailyChallenge>,
+    mut simulation: ResMut<Simulation>,
+) {
+    if *mode == GameMode::DailyChallenge {
+        commands.insert_resource(RunRng::from_seed(challenge.seed));
+        simulation.modifiers = challenge.modifiers.clone();
+    } else {
+        commands.insert_resource(RunRng::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_challenge_is_deterministic() {
+        let a = DailyChallenge::for_day(20_000);
+        let b = DailyChallenge::for_day(20_000);
+        assert_eq!(a.seed, b.seed);
+        assert_eq!(a.theme, b.theme);
+        assert_eq!(a.modifiers, b.modifiers);
+        assert_eq!(a.date(), "2024-10-04");
+
+        // Consecutive days get unrelated seeds
+        assert_ne!(a.seed, DailyChallenge::for_day(20_001).seed);
+    }
+}
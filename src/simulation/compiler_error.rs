@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::simulation::GameTime;
+use crate::simulation::RunRng;
+use crate::simulation::Simulation;
+use crate::state::AppState;
+use crate::ui::CodeTyper;
+use crate::AppSet;
+
+pub struct CompilerErrorPlugin;
+
+impl Plugin for CompilerErrorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CompilerErrorConfig>()
+            .register_type::<CompilerErrors>()
+            .init_resource::<CompilerErrors>()
+            .add_systems(
+                Update,
+                trigger_compiler_errors
+                    .in_set(AppSet::Simulate)
+                    .run_if(in_state(AppState::EditorScreen)),
+            );
+    }
+}
+
+#[derive(Default, Reflect, Serialize, Deserialize)]
+pub struct CompilerErrorConfig {
+    /// Compiler errors per second of game time, per unit of technical debt.
+    rate_per_tech_debt: f64,
+    /// The maximum compiler errors per second of game time.
+    rate_max: f64,
+    /// The number of Backspace presses needed to delete the broken code instead of fixing it.
+    backspaces: usize,
+    /// The compiler errors to choose from.
+    errors: Vec<CompilerErrorTemplate>,
+}
+
+#[derive(Default, Reflect, Serialize, Deserialize)]
+struct CompilerErrorTemplate {
+    code: String,
+    message: String,
+    fix: String,
+}
+
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct CompilerErrors {
+    /// Progress towards the next compiler error, which happens at 1.
+    progress: f64,
+    /// The compiler error that must be resolved before any more lines are typed.
+    pub active: Option<CompilerError>,
+}
+
+#[derive(Reflect)]
+pub struct CompilerError {
+    pub code: String,
+    pub message: String,
+    /// The code that resolves the error when typed.
+    pub fix: String,
+    /// The number of characters of the fix typed so far.
+    pub typed: usize,
+    /// The number of Backspace presses left to delete the broken code.
+    pub backspaces: usize,
+}
+
+impl CompilerError {
+    /// Types a character of the fix or presses Backspace. Returns true if the error was resolved.
+    pub fn press(&mut self, c: char) -> bool {
+        if c == '\u{8}' {
+            self.backspaces = self.backspaces.saturating_sub(1);
+        } else if self.fix.chars().nth(self.typed) == Some(c) {
+            self.typed += 1;
+        }
+        self.backspaces == 0 || self.typed >= self.fix.chars().count()
+    }
+}
+
+fn trigger_compiler_errors(
+    time: Res<GameTime>,
+    config: Res<Config>,
+    simulation: Res<Simulation>,
+    mut errors: ResMut<CompilerErrors>,
+    mut rng: ResMut<RunRng>,
+    typer_query: Query<(), With<CodeTyper>>,
+) {
+    // Errors can only be resolved in the code panel
+    if errors.active.is_some() || typer_query.is_empty() {
+        return;
+    }
+
+    let config = &config.compiler_error;
+    let rate = (simulation.effective_tech_debt()
+        * config.rate_per_tech_debt
+        * simulation.compiler_error_multiplier)
+        .min(config.rate_max);
+    errors.progress += rate * time.delta_seconds_f64();
+    if errors.progress < 1.0 {
+        return;
+    }
+    errors.progress = 0.0;
+
+    let Some(template) = config.errors.choose(&mut rng.compiler_errors) else {
+        return;
+    };
+    errors.active = Some(CompilerError {
+        code: template.code.clone(),
+        message: template.message.clone(),
+        fix: template.fix.clone(),
+        typed: 0,
+        backspaces: config.backspaces,
+    });
+}
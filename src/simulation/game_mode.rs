@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GameMode>().init_resource::<GameMode>();
+    }
+}
+
+/// The way the editor screen is played, chosen from the title screen.
+#[derive(Resource, Reflect, Default, Copy, Clone, PartialEq, Eq, Debug)]
+#[reflect(Resource)]
+pub enum GameMode {
+    /// A timed jam that ends with a submission.
+    #[default]
+    Jam,
+    /// A timed jam with the seed, theme, and modifiers of the day.
+    DailyChallenge,
+    /// An endless toy with no submission, uncapped upgrades, and live settings.
+    Sandbox,
+}
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use strum::EnumIter;
+
+use crate::state::AppState;
+use crate::AppSet;
+
+pub struct GameTimePlugin;
+
+impl Plugin for GameTimePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GameTime>()
+            .init_resource::<GameTime>()
+            .add_systems(OnExit(AppState::EditorScreen), stop_game_time)
+            .add_systems(
+                Update,
+                tick_game_time
+                    .in_set(AppSet::Start)
+                    .run_if(in_state(AppState::EditorScreen)),
+            );
+    }
+}
+
+/// How fast game time passes relative to real time.
+#[derive(Reflect, Default, Copy, Clone, PartialEq, Eq, Debug, EnumIter)]
+pub enum GameSpeed {
+    Paused,
+    #[default]
+    Normal,
+    Double,
+    Quadruple,
+}
+
+impl GameSpeed {
+    pub fn multiplier(self) -> f64 {
+        match self {
+            Self::Paused => 0.0,
+            Self::Normal => 1.0,
+            Self::Double => 2.0,
+            Self::Quadruple => 4.0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Paused => "Pause",
+            Self::Normal => "1x",
+            Self::Double => "2x",
+            Self::Quadruple => "4x",
+        }
+    }
+}
+
+/// The clock that drives the simulation, which only runs in the editor screen. Unlike
+/// `Time<Virtual>`, this can be paused or sped up without affecting the UI.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct GameTime {
+    pub speed: GameSpeed,
+    delta: Duration,
+    elapsed: Duration,
+}
+
+impl GameTime {
+    pub fn is_paused(&self) -> bool {
+        self.speed == GameSpeed::Paused
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    pub fn delta_seconds_f64(&self) -> f64 {
+        self.delta.as_secs_f64()
+    }
+
+    pub fn elapsed_seconds_f64(&self) -> f64 {
+        self.elapsed.as_secs_f64()
+    }
+}
+
+fn tick_game_time(time: Res<Time>, mut game_time: ResMut<GameTime>) {
+    let delta = time.delta().mul_f64(game_time.speed.multiplier());
+    game_time.delta = delta;
+    game_time.elapsed += delta;
+}
+
+/// Keeps the simulation still outside of the editor screen.
+fn stop_game_time(mut game_time: ResMut<GameTime>) {
+    game_time.delta = Duration::ZERO;
+}
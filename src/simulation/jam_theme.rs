@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use serde::Deserialize;
+use serde::Serialize;
+use strum::EnumIter;
+use strum::IntoEnumIterator;
+
+use crate::simulation::Simulation;
+use crate::util::pretty_num;
+
+pub struct JamThemePlugin;
+
+impl Plugin for JamThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<JamTheme>().init_resource::<JamTheme>();
+    }
+}
+
+/// The theme of the jam, which decides how Theme Interpretation is scored.
+#[derive(
+    Resource,
+    Reflect,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+#[reflect(Resource)]
+pub enum JamTheme {
+    #[default]
+    Entities,
+    LinesOfCode,
+    Minimalism,
+    Speedrun,
+}
+
+impl JamTheme {
+    pub fn random(self, mut rng: impl Rng) -> Self {
+        // Always pick a different theme
+        Self::iter()
+            .filter(|&theme| theme != self)
+            .choose(&mut rng)
+            .unwrap_or(self)
+    }
+
+    pub fn next(self) -> Self {
+        Self::iter()
+            .cycle()
+            .skip_while(|&theme| theme != self)
+            .nth(1)
+            .unwrap()
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Entities => "That's a LOT of entities!",
+            Self::LinesOfCode => "Lines of Code",
+            Self::Minimalism => "Minimalism",
+            Self::Speedrun => "Speedrun",
+        }
+    }
+
+    /// The tutorial line that explains how Theme Interpretation is scored.
+    pub fn tutorial_text(self) -> &'static str {
+        match self {
+            Self::Entities => "\"I should spawn a lot of entities for a higher Theme Interpretation score... Is 50 enough?\"",
+            Self::LinesOfCode => "\"I should write a lot of code for a higher Theme Interpretation score. Every line counts!\"",
+            Self::Minimalism => "\"I should keep it pretty and keep the entity count low for a higher Theme Interpretation score.\"",
+            Self::Speedrun => "\"I should submit as early as I can for a higher Theme Interpretation score.\"",
+        }
+    }
+
+    /// The value that the Theme Interpretation score is calculated from.
+    /// `elapsed` is the number of seconds spent in the editor.
+    pub fn input(self, simulation: &Simulation, elapsed: f64) -> f64 {
+        match self {
+            Self::Entities => (simulation.entities.abs() + 1.0).log10(),
+            Self::LinesOfCode => (simulation.total_lines.abs() + 1.0).log10(),
+            Self::Minimalism => {
+                simulation.presentation_score - (simulation.entities.abs() + 1.0).log10()
+            },
+            Self::Speedrun => -elapsed / 60.0,
+        }
+    }
+
+    /// Describes how the input was calculated.
+    pub fn input_text(self, simulation: &Simulation, elapsed: f64) -> String {
+        let input = self.input(simulation, elapsed);
+        match self {
+            Self::Entities => format!(
+                "{} entities\nlog10(entities + 1) = {input:.2}",
+                pretty_num(simulation.entities.floor()),
+            ),
+            Self::LinesOfCode => format!(
+                "{} total lines\nlog10(total lines + 1) = {input:.2}",
+                pretty_num(simulation.total_lines.floor()),
+            ),
+            Self::Minimalism => format!(
+                "{} presentation, {} entities\npresentation - log10(entities + 1) = {input:.2}",
+                pretty_num(simulation.presentation_score),
+                pretty_num(simulation.entities.floor()),
+            ),
+            Self::Speedrun => format!("Submitted after {:.1} minutes", -input),
+        }
+    }
+
+    /// Describes what it takes to reach the given input.
+    pub fn threshold_text(self, input: f64) -> String {
+        match self {
+            Self::Entities => format!("{} entities", pretty_num((10f64.powf(input) - 1.0).ceil())),
+            Self::LinesOfCode => format!(
+                "{} total lines",
+                pretty_num((10f64.powf(input) - 1.0).ceil()),
+            ),
+            Self::Minimalism => format!("{input:.2} minimalism"),
+            Self::Speedrun => format!("{:.1} minutes", -input),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::simulation::GameTime;
+use crate::simulation::Simulation;
+use crate::simulation::TypingSpeed;
+use crate::state::AppState;
+use crate::upgrade::UpgradeEvent;
+use crate::upgrade::UpgradeKind;
+use crate::upgrade::UpgradeList;
+use crate::AppSet;
+
+pub struct RunStatsPlugin;
+
+impl Plugin for RunStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<IncomeSource>()
+            .register_type::<RunStats>()
+            .init_resource::<RunStats>()
+            .add_systems(
+                Update,
+                (
+                    sample_run_stats,
+                    record_upgrade_installs.run_if(on_event::<UpgradeEvent>()),
+                )
+                    .in_set(AppSet::Update)
+                    .run_if(in_state(AppState::EditorScreen)),
+            );
+    }
+}
+
+/// Where a batch of lines or entities came from.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IncomeSource {
+    /// Lines typed by the player.
+    Typing,
+    /// Lines typed by `PassiveCodeTyper::chars`.
+    PassiveTyper,
+    /// Lines typed by `PassiveCodeTyper::chars_per_entity`.
+    Llm,
+    /// Entities spawned by clicking inside the scene view.
+    Click,
+    /// Entities spawned by `PassiveEntitySpawner`.
+    EntitySpawner,
+    /// Entities spawned by `Simulation::entity_spawn_per_line`.
+    LineSpawner,
+    /// Lines or entities produced all at once by installing an upgrade.
+    Upgrade(UpgradeKind),
+    /// Lines produced by the debug cheats.
+    #[cfg(feature = "dev")]
+    Cheat,
+}
+
+impl IncomeSource {
+    pub fn label(self, upgrade_list: &UpgradeList) -> String {
+        match self {
+            Self::Typing => "Typing".to_string(),
+            Self::PassiveTyper => "Coding LLM".to_string(),
+            Self::Llm => "Entities writing code".to_string(),
+            Self::Click => "Clicking".to_string(),
+            Self::EntitySpawner => "EntitySpawnerPlugin".to_string(),
+            Self::LineSpawner => "Entities per line".to_string(),
+            Self::Upgrade(kind) => upgrade_list[kind].name.clone(),
+            #[cfg(feature = "dev")]
+            Self::Cheat => "Cheats".to_string(),
+        }
+    }
+}
+
+/// A snapshot of the simulation at some point during the run.
+#[derive(Reflect, Clone, Copy, Default)]
+pub struct RunStatsSample {
+    /// Seconds of game time since the start of the run.
+    pub time: f64,
+    pub lines: f64,
+    pub entities: f64,
+    pub tech_debt: f64,
+    pub total_lines: f64,
+    pub fun_score: f64,
+    pub presentation_score: f64,
+    /// The player's typing speed in words per minute.
+    pub wpm: f64,
+}
+
+/// The number of samples to keep before halving the sample rate.
+const MAX_SAMPLES: usize = 1024;
+
+/// The history of the current run.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct RunStats {
+    /// Seconds of game time since the start of the run.
+    pub elapsed: f64,
+    /// Time between samples, doubled each time the samples are thinned out.
+    pub timer: Timer,
+    /// Evenly spaced samples since the start of the run, up to `MAX_SAMPLES`.
+    pub samples: Vec<RunStatsSample>,
+    /// Total lines produced by each source, after multipliers.
+    pub lines_by_source: HashMap<IncomeSource, f64>,
+    /// Total entities produced by each source, after multipliers.
+    pub entities_by_source: HashMap<IncomeSource, f64>,
+    /// A list of (time, upgrade) for each installed upgrade that appears in the outline.
+    pub upgrades: Vec<(f64, UpgradeKind)>,
+    /// Total Fun score contributed by each installed upgrade.
+    pub fun_by_upgrade: HashMap<UpgradeKind, f64>,
+    /// Total Presentation score contributed by each installed upgrade.
+    pub presentation_by_upgrade: HashMap<UpgradeKind, f64>,
+    /// Presentation score from the sets completed in the skin collection.
+    pub collection_bonus: f64,
+    /// The highest typing speed in words per minute.
+    pub peak_wpm: f64,
+}
+
+impl Default for RunStats {
+    fn default() -> Self {
+        Self {
+            elapsed: 0.0,
+            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            samples: vec![],
+            lines_by_source: default(),
+            entities_by_source: default(),
+            upgrades: vec![],
+            fun_by_upgrade: default(),
+            presentation_by_upgrade: default(),
+            collection_bonus: 0.0,
+            peak_wpm: 0.0,
+        }
+    }
+}
+
+impl RunStats {
+    pub fn add_lines(&mut self, source: IncomeSource, count: f64) {
+        *self.lines_by_source.entry(source).or_default() += count;
+    }
+
+    pub fn add_entities(&mut self, source: IncomeSource, count: f64) {
+        *self.entities_by_source.entry(source).or_default() += count;
+    }
+
+    /// Adds a sample, dropping every other sample and halving the sample rate if there are too
+    /// many, so long runs use bounded memory.
+    fn push_sample(&mut self, sample: RunStatsSample) {
+        self.samples.push(sample);
+        if self.samples.len() <= MAX_SAMPLES {
+            return;
+        }
+
+        let mut i = 0;
+        self.samples.retain(|_| {
+            i += 1;
+            i % 2 == 1
+        });
+        let duration = self.timer.duration() * 2;
+        self.timer.set_duration(duration);
+    }
+
+    /// Returns the (key, value) pairs of the given map, sorted from largest to smallest value.
+    pub fn ranked<K: Copy>(map: &HashMap<K, f64>) -> Vec<(K, f64)> {
+        let mut ranked = map.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+    }
+}
+
+fn sample_run_stats(
+    time: Res<GameTime>,
+    simulation: Res<Simulation>,
+    speed: Res<TypingSpeed>,
+    mut stats: ResMut<RunStats>,
+) {
+    stats.elapsed += time.delta_seconds_f64();
+    stats.peak_wpm = stats.peak_wpm.max(speed.wpm);
+    if !stats.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let sample = RunStatsSample {
+        time: stats.elapsed,
+        lines: simulation.lines,
+        entities: simulation.entities,
+        tech_debt: simulation.tech_debt,
+        total_lines: simulation.total_lines,
+        fun_score: simulation.fun_score,
+        presentation_score: simulation.presentation_score,
+        wpm: speed.wpm,
+    };
+    stats.push_sample(sample);
+}
+
+fn record_upgrade_installs(
+    mut events: EventReader<UpgradeEvent>,
+    upgrade_list: Res<UpgradeList>,
+    mut stats: ResMut<RunStats>,
+) {
+    for event in events.read() {
+        let upgrade = &upgrade_list[event.kind];
+        if !upgrade.no_outline {
+            let time = stats.elapsed;
+            stats.upgrades.push((time, event.kind));
+        }
+        if upgrade.fun_score != 0.0 {
+            *stats.fun_by_upgrade.entry(event.kind).or_default() += upgrade.fun_score;
+        }
+        if upgrade.presentation_score != 0.0 {
+            *stats.presentation_by_upgrade.entry(event.kind).or_default() +=
+                upgrade.presentation_score;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_are_thinned_out() {
+        let mut stats = RunStats::default();
+        for time in 0..=MAX_SAMPLES {
+            stats.push_sample(RunStatsSample {
+                time: time as f64,
+                ..default()
+            });
+        }
+
+        assert_eq!(stats.samples.len(), MAX_SAMPLES / 2 + 1);
+        assert!(stats
+            .samples
+            .windows(2)
+            .all(|w| w[1].time - w[0].time == 2.0));
+        assert_eq!(stats.timer.duration().as_secs_f64(), 2.0);
+    }
+}
@@ -1,5 +1,11 @@
 //! Module responsible for score calculation.
+use bevy::utils::HashMap;
+use serde::Deserialize;
+use serde::Serialize;
+
 use super::*;
+use crate::simulation::JamTheme;
+use crate::util::gen_normal;
 
 /// - x: Input
 /// - l: Max value
@@ -19,33 +25,287 @@ fn calculate_score(x: f64, lower: f64, upper: f64) -> f64 {
     1.0 + logistic_function(x, 4.0, k, x_0)
 }
 
+/// Inverse of `calculate_score`: returns the x that results in the given score.
+/// Returns None if the score is not strictly between 1 and 5.
+fn calculate_score_inverse(score: f64, lower: f64, upper: f64) -> Option<f64> {
+    if score <= 1.0 || score >= 5.0 {
+        return None;
+    }
+    let k = 4.0 / (upper - lower);
+    let x_0 = (upper + lower) / 2.0;
+    Some(x_0 - (4.0 / (score - 1.0) - 1.0).ln() / k)
+}
+
+/// The input range of the score curve for a single category.
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy)]
+pub struct ScoreCurve {
+    /// The input that results in a score of approximately 1.477.
+    lower: f64,
+    /// The input that results in a score of approximately 4.523.
+    upper: f64,
+}
+
+impl Default for ScoreCurve {
+    fn default() -> Self {
+        Self {
+            lower: 0.0,
+            upper: 50.0,
+        }
+    }
+}
+
+impl ScoreCurve {
+    /// Returns false if the range is empty or inverted, which would break the score.
+    fn is_valid(self) -> bool {
+        self.lower < self.upper
+    }
+
+    fn score(self, x: f64) -> f64 {
+        calculate_score(x, self.lower, self.upper)
+    }
+
+    fn inverse(self, score: f64) -> Option<f64> {
+        calculate_score_inverse(score, self.lower, self.upper)
+    }
+}
+
+#[derive(Default, Reflect, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    /// The score curve for Fun, with `Simulation::fun_score` as input.
+    fun_curve: ScoreCurve,
+    /// The score curve for Presentation, with `Simulation::presentation_score` as input.
+    presentation_curve: ScoreCurve,
+    /// The score curve for Theme Interpretation for each theme, with `JamTheme::input` as input.
+    theme_curves: HashMap<JamTheme, ScoreCurve>,
+    /// The weights of Fun, Presentation, and Theme Interpretation in the Overall score.
+    overall_weights: [f64; 3],
+
+    /// Seconds of development time per rating received.
+    seconds_per_rating: f64,
+    min_ratings: f64,
+    max_ratings: f64,
+    /// The standard deviation of a single rater's vote around the calculated score.
+    pub rater_noise: f64,
+}
+
+impl ScoringConfig {
+    /// Replaces the invalid score curves with the default curve.
+    pub fn validate(&mut self) {
+        let curves = [
+            ("Fun".to_string(), &mut self.fun_curve),
+            ("Presentation".to_string(), &mut self.presentation_curve),
+        ]
+        .into_iter()
+        .chain(
+            self.theme_curves
+                .iter_mut()
+                .map(|(theme, curve)| (format!("theme {theme:?}"), curve)),
+        );
+        for (name, curve) in curves {
+            if !curve.is_valid() {
+                warn!("Invalid score curve for {name}: upper must be greater than lower");
+                *curve = default();
+            }
+        }
+    }
+
+    /// Returns the number of ratings received for the given seconds of development time.
+    pub fn ratings(&self, elapsed: f64) -> usize {
+        (elapsed / self.seconds_per_rating)
+            .clamp(self.min_ratings, self.max_ratings)
+            .floor() as usize
+    }
+
+    /// Returns the Overall weights normalized to sum to 1.
+    fn normalized_weights(&self) -> [f64; 3] {
+        let total_weight = self.overall_weights.iter().sum::<f64>();
+        if total_weight <= 0.0 {
+            return [1.0 / 3.0; 3];
+        }
+        self.overall_weights.map(|weight| weight / total_weight)
+    }
+
+    /// Returns the Theme Interpretation score curve for the given theme.
+    fn theme_curve(&self, theme: JamTheme) -> ScoreCurve {
+        self.theme_curves.get(&theme).copied().unwrap_or_else(|| {
+            warn!("Missing score curve for theme {theme:?}");
+            default()
+        })
+    }
+
+    /// Returns the weighted average of the scores for Fun, Presentation, and Theme Interpretation.
+    pub fn overall(&self, scores: [f64; 3]) -> f64 {
+        scores
+            .iter()
+            .zip(self.normalized_weights())
+            .map(|(score, weight)| score * weight)
+            .sum()
+    }
+}
+
+/// The number of 1 to 5 star votes received in a single category.
+#[derive(Reflect, Clone, Copy, Default)]
+pub struct StarVotes(pub [usize; 5]);
+
+impl StarVotes {
+    /// Simulates the given number of raters voting around the given score.
+    pub fn simulate(score: f64, ratings: usize, noise: f64, mut rng: impl Rng) -> Self {
+        if score.is_nan() {
+            warn!("Simulating votes for a NaN score");
+            return Self([ratings, 0, 0, 0, 0]);
+        }
+
+        let mut votes = Self::default();
+        for _ in 0..ratings {
+            let stars = (score + noise * gen_normal(&mut rng))
+                .round()
+                .clamp(1.0, 5.0);
+            votes.0[(stars as usize).clamp(1, 5) - 1] += 1;
+        }
+        votes
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.iter().sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        let count = self.count();
+        if count == 0 {
+            return 0.0;
+        }
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| (i + 1) as f64 * n as f64)
+            .sum::<f64>()
+            / count as f64
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        let count = self.count();
+        if count == 0 {
+            return 0.0;
+        }
+        let mean = self.mean();
+        let variance = self
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| n as f64 * ((i + 1) as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        variance.sqrt()
+    }
+}
+
+/// The result of simulated raters voting on a submission.
+/// Each array holds Fun, Presentation, Theme Interpretation, and Overall in that order.
+#[derive(Reflect, Clone, Default)]
+pub struct Ratings {
+    /// The star votes for each category, except Overall.
+    pub votes: [StarVotes; 3],
+    /// The mean vote for each category.
+    pub scores: [f64; 4],
+    /// The standard deviation of the votes for each category.
+    pub std_devs: [f64; 4],
+    /// The calculated score for each category, before rater noise.
+    pub raw_scores: [f64; 4],
+}
+
 impl Simulation {
-    /// Returns the calculated score (between 1 and 5) for each category.
+    /// Returns the calculated score (between 1 and 5) for each category, where `elapsed` is the
+    /// number of seconds spent in the editor.
     /// 1. Fun
     /// 2. Presentation
     /// 3. Theme Interpretation
     /// 4. Overall
-    pub fn calculate_scores(&self, ratings: f64) -> [f64; 4] {
-        let mut scores: [f64; 4] = [
-            // Fun
-            calculate_score(self.fun_score, 0.0, 28.0),
-            // Presentation
-            calculate_score(self.presentation_score, 0.0, 30.0),
-            // Theme Interpretation
-            calculate_score((self.entities.abs() + 1.0).log10(), 0.0, 50.0),
-            // Overall
-            0.0,
-        ];
-
-        // Floor scores to multiples of 1 / ratings
-        for score in &mut scores {
-            *score = (*score * ratings).floor() / ratings
+    pub fn calculate_scores(
+        &self,
+        config: &ScoringConfig,
+        theme: JamTheme,
+        elapsed: f64,
+    ) -> [f64; 4] {
+        let scores = self.calculate_raw_scores(config, theme, elapsed);
+        let [fun, presentation, theme] = scores;
+        [fun, presentation, theme, config.overall(scores)]
+    }
+
+    /// Simulates the given number of raters voting on each category.
+    pub fn rate(
+        &self,
+        config: &ScoringConfig,
+        theme: JamTheme,
+        elapsed: f64,
+        ratings: usize,
+        mut rng: impl Rng,
+    ) -> Ratings {
+        let raw_scores = self.calculate_scores(config, theme, elapsed);
+        let votes = [0, 1, 2]
+            .map(|i| StarVotes::simulate(raw_scores[i], ratings, config.rater_noise, &mut rng));
+        let [fun, presentation, theme] = votes.map(|x| x.mean());
+        let [fun_std_dev, presentation_std_dev, theme_std_dev] = votes.map(|x| x.std_dev());
+
+        // The votes in each category are independent, so the variance of the Overall
+        // vote is the weighted sum of their variances
+        let overall_std_dev = config
+            .normalized_weights()
+            .iter()
+            .zip([fun_std_dev, presentation_std_dev, theme_std_dev])
+            .map(|(weight, std_dev)| (weight * std_dev).powi(2))
+            .sum::<f64>()
+            .sqrt();
+
+        Ratings {
+            votes,
+            scores: [
+                fun,
+                presentation,
+                theme,
+                config.overall([fun, presentation, theme]),
+            ],
+            std_devs: [
+                fun_std_dev,
+                presentation_std_dev,
+                theme_std_dev,
+                overall_std_dev,
+            ],
+            raw_scores,
         }
+    }
 
-        // Calculate overall score
-        scores[3] = scores.iter().sum::<f64>() / 3.0;
+    /// Returns the unrounded score (between 1 and 5) for Fun, Presentation, and
+    /// Theme Interpretation.
+    fn calculate_raw_scores(
+        &self,
+        config: &ScoringConfig,
+        theme: JamTheme,
+        elapsed: f64,
+    ) -> [f64; 3] {
+        [
+            config.fun_curve.score(self.fun_score),
+            config.presentation_curve.score(self.presentation_score),
+            config.theme_curve(theme).score(theme.input(self, elapsed)),
+        ]
+    }
 
-        scores
+    /// Returns the value required to raise the score by 0.5 for each category, or None if
+    /// it's out of reach.
+    /// 1. Fun score
+    /// 2. Presentation score
+    /// 3. Theme input (see `JamTheme::input`)
+    pub fn next_score_thresholds(
+        &self,
+        config: &ScoringConfig,
+        theme: JamTheme,
+        elapsed: f64,
+    ) -> [Option<f64>; 3] {
+        let [fun, presentation, theme_score] = self.calculate_raw_scores(config, theme, elapsed);
+        [
+            config.fun_curve.inverse(fun + 0.5),
+            config.presentation_curve.inverse(presentation + 0.5),
+            config.theme_curve(theme).inverse(theme_score + 0.5),
+        ]
     }
 }
 
@@ -78,4 +338,203 @@ mod tests {
         assert!(f64::abs(calculate_score(f64::INFINITY, -1e32, 1e32) - 5.0) < 0.001);
         assert!(f64::abs(calculate_score(-f64::INFINITY, -1e32, 1e32) - 1.0) < 0.001);
     }
+
+    #[test]
+    fn calculate_score_inverse_test() {
+        // Low
+        assert_eq!(
+            format!("{:.3}", calculate_score_inverse(1.477, -1.0, 1.0).unwrap()),
+            "-1.000"
+        );
+        // Midpoint
+        assert_eq!(
+            format!("{:.3}", calculate_score_inverse(3.0, -1.0, 1.0).unwrap()),
+            "0.000"
+        );
+        // High
+        assert_eq!(
+            format!("{:.3}", calculate_score_inverse(4.523, -1.0, 1.0).unwrap()),
+            "1.000"
+        );
+        // Round trip
+        for x in [-20.0, 0.0, 7.5, 28.0, 40.0] {
+            let score = calculate_score(x, 0.0, 28.0);
+            assert!(f64::abs(calculate_score_inverse(score, 0.0, 28.0).unwrap() - x) < 0.001);
+        }
+        // Out of range
+        assert_eq!(calculate_score_inverse(1.0, -1.0, 1.0), None);
+        assert_eq!(calculate_score_inverse(5.0, -1.0, 1.0), None);
+        assert_eq!(calculate_score_inverse(5.5, -1.0, 1.0), None);
+    }
+
+    fn test_config() -> ScoringConfig {
+        ScoringConfig {
+            fun_curve: ScoreCurve {
+                lower: -1.0,
+                upper: 1.0,
+            },
+            presentation_curve: ScoreCurve {
+                lower: 0.0,
+                upper: 10.0,
+            },
+            theme_curves: [
+                (
+                    JamTheme::Entities,
+                    ScoreCurve {
+                        lower: 0.0,
+                        upper: 2.0,
+                    },
+                ),
+                (
+                    JamTheme::Speedrun,
+                    ScoreCurve {
+                        lower: -30.0,
+                        upper: -5.0,
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            overall_weights: [1.0, 1.0, 1.0],
+            seconds_per_rating: 60.0,
+            min_ratings: 5.0,
+            max_ratings: 120.0,
+            rater_noise: 1.0,
+        }
+    }
+
+    #[test]
+    fn calculate_scores_curve_test() {
+        let config = test_config();
+        let simulation = Simulation {
+            fun_score: 1.0,
+            presentation_score: 5.0,
+            // log10(entities + 1) = 0
+            entities: 0.0,
+            ..default()
+        };
+        let scores = simulation.calculate_scores(&config, JamTheme::Entities, 0.0);
+        // High
+        assert_eq!(format!("{:.3}", scores[0]), "4.523");
+        // Midpoint
+        assert_eq!(format!("{:.3}", scores[1]), "3.000");
+        // Low
+        assert_eq!(format!("{:.3}", scores[2]), "1.477");
+        // Average
+        assert_eq!(format!("{:.3}", scores[3]), "3.000");
+    }
+
+    #[test]
+    fn calculate_scores_weights_test() {
+        let mut config = test_config();
+        let simulation = Simulation {
+            fun_score: 1.0,
+            presentation_score: 5.0,
+            entities: 0.0,
+            ..default()
+        };
+
+        // Only Fun
+        config.overall_weights = [1.0, 0.0, 0.0];
+        let scores = simulation.calculate_scores(&config, JamTheme::Entities, 0.0);
+        assert_eq!(format!("{:.3}", scores[3]), format!("{:.3}", scores[0]));
+
+        // Fun counts double
+        config.overall_weights = [2.0, 1.0, 1.0];
+        let scores = simulation.calculate_scores(&config, JamTheme::Entities, 0.0);
+        assert_eq!(
+            format!("{:.3}", scores[3]),
+            format!("{:.3}", (2.0 * scores[0] + scores[1] + scores[2]) / 4.0),
+        );
+
+        // No weights falls back to the plain average
+        config.overall_weights = [0.0, 0.0, 0.0];
+        let scores = simulation.calculate_scores(&config, JamTheme::Entities, 0.0);
+        assert_eq!(
+            format!("{:.3}", scores[3]),
+            format!("{:.3}", (scores[0] + scores[1] + scores[2]) / 3.0),
+        );
+    }
+
+    #[test]
+    fn calculate_scores_theme_test() {
+        let config = test_config();
+        let simulation = Simulation::default();
+
+        // Low
+        let scores = simulation.calculate_scores(&config, JamTheme::Speedrun, 30.0 * 60.0);
+        assert_eq!(format!("{:.3}", scores[2]), "1.477");
+        // Midpoint
+        let scores = simulation.calculate_scores(&config, JamTheme::Speedrun, 17.5 * 60.0);
+        assert_eq!(format!("{:.3}", scores[2]), "3.000");
+        // High
+        let scores = simulation.calculate_scores(&config, JamTheme::Speedrun, 5.0 * 60.0);
+        assert_eq!(format!("{:.3}", scores[2]), "4.523");
+
+        // Missing theme falls back to a valid curve
+        let scores = simulation.calculate_scores(&config, JamTheme::LinesOfCode, 0.0);
+        assert!(scores.iter().all(|score| score.is_finite()));
+    }
+
+    #[test]
+    fn validate_test() {
+        let mut config = test_config();
+        config.fun_curve = ScoreCurve {
+            lower: 1.0,
+            upper: 1.0,
+        };
+        config.validate();
+
+        // Invalid curves are replaced
+        assert!(config.fun_curve.is_valid());
+        assert_eq!(config.fun_curve.upper, 50.0);
+        // Valid curves are kept
+        assert_eq!(config.presentation_curve.upper, 10.0);
+    }
+
+    #[test]
+    fn star_votes_test() {
+        let votes = StarVotes([1, 0, 2, 0, 1]);
+        assert_eq!(votes.count(), 4);
+        assert_eq!(format!("{:.3}", votes.mean()), "3.000");
+        assert_eq!(format!("{:.3}", votes.std_dev()), "1.414");
+
+        // No votes
+        assert_eq!(StarVotes::default().mean(), 0.0);
+        assert_eq!(StarVotes::default().std_dev(), 0.0);
+    }
+
+    #[test]
+    fn star_votes_simulate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        // Without noise, every rater votes the rounded score
+        let votes = StarVotes::simulate(3.4, 20, 0.0, &mut rng);
+        assert_eq!(votes.0, [0, 0, 20, 0, 0]);
+
+        // Votes are clamped between 1 and 5 stars
+        let votes = StarVotes::simulate(10.0, 20, 1.0, &mut rng);
+        assert_eq!(votes.0, [0, 0, 0, 0, 20]);
+
+        // With noise, the mean converges to the score
+        let votes = StarVotes::simulate(3.4, 100_000, 1.0, &mut rng);
+        assert_eq!(votes.count(), 100_000);
+        assert!(f64::abs(votes.mean() - 3.4) < 0.05);
+        assert!(votes.std_dev() > 0.5);
+
+        // A NaN score gets the lowest votes instead of panicking
+        let votes = StarVotes::simulate(f64::NAN, 20, 1.0, &mut rng);
+        assert_eq!(votes.0, [20, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn ratings_test() {
+        let config = test_config();
+        // Min
+        assert_eq!(config.ratings(0.0), 5);
+        // Floored
+        assert_eq!(config.ratings(659.0), 10);
+        // Max
+        assert_eq!(config.ratings(1e9), 120);
+    }
 }
@@ -1,11 +1,17 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::ops::Range;
 
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
 use rand::Rng;
+use serde::Deserialize;
+use serde::Serialize;
+use tap::TapFallible;
 
+use crate::simulation::GameTime;
+use crate::simulation::RunRng;
 use crate::simulation::Simulation;
 use crate::util::gen_color;
 use crate::AppRoot;
@@ -17,301 +23,457 @@ impl Plugin for SpritePackPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<SpritePackAssets>()
             .register_type::<SpritePackEvent>()
+            .register_type::<SpriteAnimation>()
+            .register_type::<PaletteConfig>()
             .add_event::<SpritePackEvent>()
+            .insert_resource(AtlasList::discover())
             .init_collection::<SpritePackAssets>()
-            .init_resource::<AtlasList>()
-            .add_systems(Startup, load_atlas_list)
             .add_systems(
                 Update,
-                apply_sprite_pack
-                    .in_set(AppSet::End)
-                    .run_if(on_event::<SpritePackEvent>()),
+                (
+                    animate_sprites.in_set(AppSet::Update),
+                    apply_sprite_pack
+                        .in_set(AppSet::End)
+                        .run_if(on_event::<SpritePackEvent>()),
+                ),
             );
     }
 }
 
-#[derive(AssetCollection, Resource, Reflect, Default)]
+/// The folder that sprite packs are discovered in, relative to the assets folder.
+const SPRITE_PACK_DIR: &str = "image/entity";
+
+/// The ID of the sprite pack for plain squares, which is always loaded first.
+const NONE_PACK: &str = "none";
+
+/// A sprite pack, loaded from `pack.ron` in the sprite pack's folder.
+#[derive(Deserialize)]
+struct SpritePackManifest {
+    /// The name of the sprite pack shown to the player.
+    name: String,
+    sheets: Vec<SheetManifest>,
+}
+
+/// A sprite sheet in a sprite pack, split into a grid of tiles.
+#[derive(Deserialize)]
+struct SheetManifest {
+    /// The path to the image, relative to the sprite pack's folder.
+    path: String,
+    tile_size: (f32, f32),
+    columns: usize,
+    rows: usize,
+    /// The tiles to generate skins from, or every tile if None.
+    #[serde(default)]
+    tiles: Option<Vec<usize>>,
+    /// Ranges of tiles to leave out, e.g. empty tiles.
+    #[serde(default)]
+    exclude: Vec<Range<usize>>,
+    /// How to tint the tiles.
+    #[serde(default)]
+    tint: Tint,
+    /// How to tint specific tiles, overriding `tint`.
+    #[serde(default)]
+    tile_tints: HashMap<usize, Tint>,
+    /// Animated tiles to generate skins from. Their frames are left out of `tiles`.
+    #[serde(default)]
+    animations: Vec<AnimationManifest>,
+}
+
+/// An animated tile in a sprite sheet.
+#[derive(Deserialize)]
+struct AnimationManifest {
+    /// The tiles to show in order, looping back to the first.
+    frames: Vec<usize>,
+    /// Frames per second of game time.
+    fps: f64,
+}
+
+/// How to tint a tile when generating a skin.
+#[derive(Default, Deserialize, Copy, Clone, PartialEq)]
+enum Tint {
+    /// Keep the tile's original colors.
+    #[default]
+    Original,
+    /// Tint the tile with a random color from `Simulation::entity_colors`.
+    Random,
+    /// Tint the tile with a specific color.
+    Fixed(Color),
+}
+
+impl Tint {
+    fn color(self, colors: &[Color], mut rng: impl Rng) -> Color {
+        match self {
+            Self::Original => Color::WHITE,
+            Self::Random => colors
+                .choose(&mut rng)
+                .copied()
+                .unwrap_or_else(|| gen_color(&mut rng)),
+            Self::Fixed(color) => color,
+        }
+    }
+}
+
+/// The manifest of each sprite pack bundled into the binary, by folder name.
+const BUNDLED_PACKS: [(&str, &str); 5] = [
+    (
+        "none",
+        include_str!("../../assets/image/entity/none/pack.ron"),
+    ),
+    (
+        "text",
+        include_str!("../../assets/image/entity/text/pack.ron"),
+    ),
+    (
+        "1-bit",
+        include_str!("../../assets/image/entity/1-bit/pack.ron"),
+    ),
+    (
+        "rpg",
+        include_str!("../../assets/image/entity/rpg/pack.ron"),
+    ),
+    (
+        "ninja",
+        include_str!("../../assets/image/entity/ninja/pack.ron"),
+    ),
+];
+
+impl SpritePackManifest {
+    /// Finds and reads the manifest of every sprite pack, by folder name.
+    #[cfg(feature = "web")]
+    fn discover() -> Vec<(String, String)> {
+        // Folders can't be listed on web, so only the bundled sprite packs are available
+        Self::bundled()
+    }
+
+    /// Finds and reads the manifest of every sprite pack, by folder name.
+    #[cfg(not(feature = "web"))]
+    fn discover() -> Vec<(String, String)> {
+        // Resolve the assets folder the same way the asset server does, so that discovery doesn't
+        // depend on the working directory
+        let dir = bevy::asset::io::file::FileAssetReader::get_base_path()
+            .join("assets")
+            .join(SPRITE_PACK_DIR);
+        let manifests = std::fs::read_dir(&dir)
+            .tap_err(|e| error!("Reading {dir:?}: {e}"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let manifest = std::fs::read_to_string(entry.path().join("pack.ron")).ok()?;
+                Some((entry.file_name().to_string_lossy().into_owned(), manifest))
+            })
+            .collect::<Vec<_>>();
+
+        if manifests.is_empty() {
+            warn!("No sprite packs found in {dir:?}, using the bundled sprite packs");
+            return Self::bundled();
+        }
+        manifests
+    }
+
+    /// Returns the manifest of each bundled sprite pack, by folder name.
+    fn bundled() -> Vec<(String, String)> {
+        BUNDLED_PACKS
+            .map(|(id, manifest)| (id.to_string(), manifest.to_string()))
+            .into()
+    }
+}
+
+/// The sprite sheets of all sprite packs, loaded into texture atlases.
+#[derive(Resource, Reflect, Default)]
 #[reflect(Resource)]
 pub struct SpritePackAssets {
-    // None
-    #[asset(texture_atlas(tile_size_x = 1.0, tile_size_y = 1.0, rows = 1, columns = 1))]
-    #[asset(path = "image/entity/none.png")]
-    pub none: Handle<TextureAtlas>,
-
-    // Text
-    #[asset(texture_atlas(tile_size_x = 12.0, tile_size_y = 12.0, rows = 1, columns = 94))]
-    #[asset(path = "image/entity/text/pypx-T.png")]
-    pub text: Handle<TextureAtlas>,
-
-    // 1-bit
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 17, columns = 11))]
-    #[asset(path = "image/entity/1-bit/Clothing.png")]
-    pub one_bit_clothing: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 18, columns = 14))]
-    #[asset(path = "image/entity/1-bit/Creatures.png")]
-    pub one_bit_creatures: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 5, columns = 17))]
-    #[asset(path = "image/entity/1-bit/Food.png")]
-    pub one_bit_food: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 5, columns = 8))]
-    #[asset(path = "image/entity/1-bit/Gems-Jewels-and-Money.png")]
-    pub one_bit_gems_jewels_and_money: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 4, columns = 8))]
-    #[asset(path = "image/entity/1-bit/Instruments.png")]
-    pub one_bit_instruments: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 9, columns = 17))]
-    #[asset(path = "image/entity/1-bit/Jewelry.png")]
-    pub one_bit_jewelry: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 9, columns = 4))]
-    #[asset(path = "image/entity/1-bit/Misc-Future.png")]
-    pub one_bit_misc_future: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 10, columns = 6))]
-    #[asset(path = "image/entity/1-bit/Misc.png")]
-    pub one_bit_misc: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 27, columns = 32))]
-    #[asset(path = "image/entity/1-bit/People.png")]
-    pub one_bit_people: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 6, columns = 4))]
-    #[asset(path = "image/entity/1-bit/Potions.png")]
-    pub one_bit_potions: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 8, columns = 9))]
-    #[asset(path = "image/entity/1-bit/Tools.png")]
-    pub one_bit_tools: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 19, columns = 21))]
-    #[asset(path = "image/entity/1-bit/UI.png")]
-    pub one_bit_ui: Handle<TextureAtlas>,
-    #[asset(texture_atlas(tile_size_x = 10.0, tile_size_y = 10.0, rows = 11, columns = 13))]
-    #[asset(path = "image/entity/1-bit/Weapons.png")]
-    pub one_bit_weapons: Handle<TextureAtlas>,
-
-    // RPG
-    #[asset(path = "image/entity/rpg/armours.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 19, columns = 9))]
-    pub rpg_armours: Handle<TextureAtlas>,
-    #[asset(path = "image/entity/rpg/books.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 12, columns = 16))]
-    pub rpg_books: Handle<TextureAtlas>,
-    #[asset(path = "image/entity/rpg/chests.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 6, columns = 8))]
-    pub rpg_chests: Handle<TextureAtlas>,
-    #[asset(path = "image/entity/rpg/consumables.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 17, columns = 44))]
-    pub rpg_consumables: Handle<TextureAtlas>,
-    #[asset(path = "image/entity/rpg/potions.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 15, columns = 21))]
-    pub rpg_potions: Handle<TextureAtlas>,
-    #[asset(path = "image/entity/rpg/weapons.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 9, columns = 8))]
-    pub rpg_weapons: Handle<TextureAtlas>,
-
-    // Ninja
-    #[asset(path = "image/entity/ninja/Animals.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 1, columns = 7))]
-    pub ninja_animals: Handle<TextureAtlas>,
-    #[asset(path = "image/entity/ninja/Characters.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 1, columns = 56))]
-    pub ninja_characters: Handle<TextureAtlas>,
-    #[asset(path = "image/entity/ninja/Items.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 1, columns = 2))]
-    pub ninja_items: Handle<TextureAtlas>,
-    #[asset(path = "image/entity/ninja/Monsters.png")]
-    #[asset(texture_atlas(tile_size_x = 16.0, tile_size_y = 16.0, rows = 1, columns = 13))]
-    pub ninja_monsters: Handle<TextureAtlas>,
+    /// The texture atlas of each atlas in the `AtlasList`, by index.
+    atlases: Vec<Handle<TextureAtlas>>,
+}
+
+impl SpritePackAssets {
+    /// Returns the texture atlas of the atlas in the `AtlasList` with the given index.
+    pub fn atlas(&self, atlas: usize) -> Handle<TextureAtlas> {
+        self.atlases.get(atlas).cloned().unwrap_or_default()
+    }
+}
+
+impl AssetCollection for SpritePackAssets {
+    fn create(world: &mut World) -> Self {
+        world.resource_scope(|world, atlas_list: Mut<AtlasList>| {
+            let asset_server = world.resource::<AssetServer>().clone();
+            let mut texture_atlases = world.resource_mut::<Assets<TextureAtlas>>();
+            let atlases = atlas_list
+                .atlases
+                .iter()
+                .map(|atlas| {
+                    texture_atlases.add(TextureAtlas::from_grid(
+                        asset_server.load(&atlas.path),
+                        atlas.tile_size,
+                        atlas.columns,
+                        atlas.rows,
+                        None,
+                        None,
+                    ))
+                })
+                .collect();
+            Self { atlases }
+        })
+    }
+
+    fn load(world: &mut World) -> Vec<UntypedHandle> {
+        let asset_server = world.resource::<AssetServer>();
+        world
+            .resource::<AtlasList>()
+            .atlases
+            .iter()
+            .map(|atlas| asset_server.load::<Image>(&atlas.path).untyped())
+            .collect()
+    }
 }
 
 /// A list of all available atlas metadata
 #[derive(Resource, Default)]
-pub struct AtlasList(Vec<Atlas>);
-
-fn load_atlas_list(mut atlas_list: ResMut<AtlasList>) {
-    atlas_list.0.extend([
-        // None
-        Atlas {
-            path: "none",
-            tiles: vec![Tile::any_color(0)],
-        },
-        // Text
-        Atlas {
-            path: "text",
-            tiles: (0..94).map(Tile::any_color).collect(),
-        },
-        // 1-bit
-        Atlas {
-            path: "one_bit_clothing",
-            tiles: [14, 19, 31].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_creatures",
-            tiles: [
-                10, 12, 15, 17, 18, 27, 30, 46, 55, 58, 75, 93, 96, 107, 128, 140, 166,
-            ]
-            .map(Tile::new)
-            .into(),
-        },
-        Atlas {
-            path: "one_bit_food",
-            tiles: [5, 12, 19, 22, 28, 35, 38, 39, 43, 55, 68, 69, 72]
-                .map(Tile::new)
-                .into(),
-        },
-        Atlas {
-            path: "one_bit_gems_jewels_and_money",
-            tiles: [0, 8].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_instruments",
-            tiles: [0].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_jewelry",
-            tiles: [24, 88].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_misc_future",
-            tiles: [32].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_misc",
-            tiles: [42, 49, 55].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_people",
-            tiles: [454, 643].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_potions",
-            tiles: [10].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_tools",
-            tiles: [0, 1, 2, 4, 10, 13, 16, 30, 34, 63].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_ui",
-            tiles: [157, 177, 231, 315].map(Tile::new).into(),
-        },
-        Atlas {
-            path: "one_bit_weapons",
-            tiles: [7, 28, 44, 92, 122, 140].map(Tile::new).into(),
-        },
-        // RPG
-        Atlas {
-            path: "rpg_armours",
-            tiles: (0..19 * 9).map(Tile::new).collect(),
-        },
-        Atlas {
-            path: "rpg_books",
-            tiles: (0..12 * 16).map(Tile::new).collect(),
-        },
-        Atlas {
-            path: "rpg_chests",
-            tiles: (0..6 * 8).map(Tile::new).collect(),
-        },
-        Atlas {
-            path: "rpg_consumables",
-            tiles: (0..12 * 44 + 22)
-                .chain(13 * 44..13 * 44 + 22)
-                .chain(14 * 44..14 * 44 + 22)
-                .chain(15 * 44..15 * 44 + 22)
-                .chain(16 * 44..16 * 44 + 22)
-                .map(Tile::new)
-                .collect(),
-        },
-        Atlas {
-            path: "rpg_potions",
-            tiles: (0..15 * 21).map(Tile::new).collect(),
-        },
-        Atlas {
-            path: "rpg_weapons",
-            tiles: (0..9 * 8).map(Tile::new).collect(),
-        },
-        // Ninja
-        Atlas {
-            path: "ninja_animals",
-            tiles: (0..7).map(Tile::new).collect(),
-        },
-        Atlas {
-            path: "ninja_characters",
-            tiles: (0..56).map(Tile::new).collect(),
-        },
-        Atlas {
-            path: "ninja_items",
-            tiles: (0..2).map(Tile::new).collect(),
-        },
-        Atlas {
-            path: "ninja_monsters",
-            tiles: (0..13).map(Tile::new).collect(),
-        },
-    ]);
+pub struct AtlasList {
+    atlases: Vec<Atlas>,
+    packs: Vec<SpritePackInfo>,
 }
 
-/// A thematically-consistent skin-generating space
-#[derive(Default, Copy, Clone)]
-#[allow(dead_code)]
-pub enum SpritePack {
-    #[default]
-    None,
-    Text,
-    OneBit,
-    Rpg,
-    Ninja,
-}
+impl AtlasList {
+    /// Loads the atlas metadata from the sprite pack manifests.
+    fn discover() -> Self {
+        let mut manifests = SpritePackManifest::discover()
+            .into_iter()
+            .filter_map(|(id, manifest)| {
+                ron::from_str::<SpritePackManifest>(&manifest)
+                    .tap_err(|e| error!("Deserializing sprite pack {id}: {e}"))
+                    .ok()
+                    .map(|manifest| (id, manifest))
+            })
+            .collect::<Vec<_>>();
+        // The default skins are from the "none" sprite pack, so it must come first
+        manifests.sort_by(|(a, _), (b, _)| (a != NONE_PACK, a).cmp(&(b != NONE_PACK, b)));
+        if manifests.first().map(|(id, _)| id.as_str()) != Some(NONE_PACK) {
+            error!("Missing the {NONE_PACK} sprite pack");
+        }
 
-impl SpritePack {
-    fn atlases(&self) -> Range<usize> {
-        match self {
-            Self::None => 0..1,
-            Self::Text => 1..2,
-            Self::OneBit => 2..15,
-            Self::Rpg => 15..21,
-            Self::Ninja => 21..25,
+        let mut this = Self::default();
+        for (id, manifest) in manifests {
+            let start = this.atlases.len();
+            for sheet in manifest.sheets {
+                let animated = sheet
+                    .animations
+                    .iter()
+                    .flat_map(|animation| animation.frames.iter().copied())
+                    .collect::<HashSet<_>>();
+                let tiles = sheet
+                    .tiles
+                    .unwrap_or_else(|| (0..sheet.columns * sheet.rows).collect())
+                    .into_iter()
+                    .filter(|index| !sheet.exclude.iter().any(|range| range.contains(index)))
+                    .filter(|index| !animated.contains(index))
+                    .map(|index| Tile::Static {
+                        index,
+                        tint: sheet.tile_tints.get(&index).copied().unwrap_or(sheet.tint),
+                    })
+                    .chain(
+                        sheet
+                            .animations
+                            .into_iter()
+                            .filter(|animation| !animation.frames.is_empty())
+                            .map(|animation| Tile::Animated {
+                                tint: sheet
+                                    .tile_tints
+                                    .get(&animation.frames[0])
+                                    .copied()
+                                    .unwrap_or(sheet.tint),
+                                frames: animation.frames,
+                                fps: animation.fps,
+                            }),
+                    )
+                    .collect::<Vec<_>>();
+                if tiles.is_empty() {
+                    warn!(
+                        "Sprite sheet {} in sprite pack {id} has no tiles",
+                        sheet.path
+                    );
+                    continue;
+                }
+
+                this.atlases.push(Atlas {
+                    path: format!("{SPRITE_PACK_DIR}/{id}/{}", sheet.path),
+                    tile_size: Vec2::new(sheet.tile_size.0, sheet.tile_size.1),
+                    columns: sheet.columns,
+                    rows: sheet.rows,
+                    tiles,
+                });
+            }
+
+            if start == this.atlases.len() {
+                warn!("Sprite pack {id} has no sprite sheets");
+                continue;
+            }
+            this.packs.push(SpritePackInfo {
+                id,
+                name: manifest.name,
+                atlases: start..this.atlases.len(),
+            });
+        }
+        info!("Loaded {} sprite packs", this.packs.len());
+
+        this
+    }
+
+    /// Returns the sprite pack with the given folder name.
+    pub fn sprite_pack(&self, id: &str) -> Option<SpritePack> {
+        self.packs
+            .iter()
+            .position(|pack| pack.id == id)
+            .map(SpritePack)
+    }
+
+    /// Returns whether the sprite pack is the plain squares that every run starts with.
+    pub fn is_none_pack(&self, sprite_pack: SpritePack) -> bool {
+        self.packs
+            .get(sprite_pack.0)
+            .is_some_and(|pack| pack.id == NONE_PACK)
+    }
+
+    /// Returns every sprite pack, in order.
+    pub fn sprite_packs(&self) -> impl Iterator<Item = SpritePack> {
+        (0..self.packs.len()).map(SpritePack)
+    }
+
+    /// Returns the name of the sprite pack shown to the player, or "" if it doesn't exist.
+    pub fn label(&self, sprite_pack: SpritePack) -> &str {
+        self.packs
+            .get(sprite_pack.0)
+            .map_or("", |pack| pack.name.as_str())
+    }
+
+    /// Returns the indices of the sprite pack's atlases, or none if it doesn't exist.
+    pub fn atlases(&self, sprite_pack: SpritePack) -> Range<usize> {
+        self.packs
+            .get(sprite_pack.0)
+            .map_or(0..0, |pack| pack.atlases.clone())
+    }
+
+    /// Returns the file name of the atlas' sprite sheet without the extension, e.g. "Monsters".
+    pub fn atlas_label(&self, atlas: usize) -> &str {
+        let path = &self.atlases[atlas].path;
+        let name = path.rsplit('/').next().unwrap_or(path);
+        name.rsplit_once('.').map_or(name, |(stem, _)| stem)
+    }
+
+    /// Returns the key of each tile in the atlas, in order.
+    pub fn tile_keys(&self, atlas: usize) -> impl Iterator<Item = TileKey> + '_ {
+        let path = &self.atlases[atlas].path;
+        self.atlases[atlas].tiles.iter().map(|tile| match tile {
+            Tile::Static { index, .. } => TileKey {
+                atlas: path.clone(),
+                index: *index,
+                animated: false,
+            },
+            Tile::Animated { frames, .. } => TileKey {
+                atlas: path.clone(),
+                index: frames[0],
+                animated: true,
+            },
+        })
+    }
+
+    /// Returns the key of the tile that the skin was generated from.
+    pub fn tile_key(&self, skin: &Skin) -> TileKey {
+        TileKey {
+            atlas: self
+                .atlases
+                .get(skin.atlas)
+                .map(|atlas| atlas.path.clone())
+                .unwrap_or_default(),
+            index: skin.index,
+            animated: skin.animation.is_some(),
         }
     }
+}
+
+/// Identifies a tile across runs, even if sprite packs are added or removed.
+#[derive(Reflect, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TileKey {
+    /// The asset path of the sprite sheet.
+    pub atlas: String,
+    /// The index of the tile in the sprite sheet, or of the first frame if animated.
+    pub index: usize,
+    pub animated: bool,
+}
 
-    fn random(&self, atlas_list: &AtlasList, mut rng: impl Rng) -> Skin {
-        let atlas_idx = *self
-            .atlases()
+struct SpritePackInfo {
+    /// The name of the sprite pack's folder.
+    id: String,
+    name: String,
+    /// The indices of the sprite pack's atlases in the `AtlasList`.
+    atlases: Range<usize>,
+}
+
+/// A thematically-consistent skin-generating space
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SpritePack(usize);
+
+impl SpritePack {
+    /// Generates a random skin, or None if the sprite pack doesn't exist.
+    fn random(&self, atlas_list: &AtlasList, colors: &[Color], mut rng: impl Rng) -> Option<Skin> {
+        let atlas_idx = *atlas_list
+            .atlases(*self)
             .collect::<Vec<_>>()
-            .choose_weighted(&mut rng, |&i| atlas_list.0[i].tiles.len())
-            .unwrap();
+            .choose_weighted(&mut rng, |&i| atlas_list.atlases[i].tiles.len())
+            .ok()?;
 
-        atlas_list.0[atlas_idx].random(&mut rng)
+        Some(atlas_list.atlases[atlas_idx].random(atlas_idx, colors, &mut rng))
     }
 }
 
 /// A view into an atlas that can be used for generating skins
-struct Tile {
-    index: usize,
-    /// The color of the generated skin, or a random color if None
-    color: Option<Color>,
+enum Tile {
+    Static {
+        index: usize,
+        tint: Tint,
+    },
+    /// A looping sequence of frames, shown once `Simulation::animated_sprites` is enabled.
+    Animated {
+        frames: Vec<usize>,
+        fps: f64,
+        tint: Tint,
+    },
 }
 
 impl Tile {
-    fn new(index: usize) -> Self {
-        Self {
-            index,
-            color: Some(Color::WHITE),
+    fn tint(&self) -> Tint {
+        match *self {
+            Self::Static { tint, .. } | Self::Animated { tint, .. } => tint,
         }
     }
-
-    fn any_color(index: usize) -> Self {
-        Self { index, color: None }
-    }
 }
 
 /// An atlas with metadata for generating skins
-pub struct Atlas {
-    path: &'static str,
+struct Atlas {
+    /// The asset path of the sprite sheet.
+    path: String,
+    tile_size: Vec2,
+    columns: usize,
+    rows: usize,
     tiles: Vec<Tile>,
 }
 
 impl Atlas {
-    fn random(&self, mut rng: impl Rng) -> Skin {
-        let tile = self.tiles.choose(&mut rng).unwrap();
+    fn random(&self, atlas: usize, colors: &[Color], mut rng: impl Rng) -> Skin {
+        let tile_idx = rng.gen_range(0..self.tiles.len());
+        let tile = &self.tiles[tile_idx];
+        let (index, animation) = match tile {
+            Tile::Static { index, .. } => (*index, None),
+            Tile::Animated { frames, .. } => (frames[0], Some(tile_idx)),
+        };
+        let tint = tile.tint();
         Skin {
-            atlas_path: self.path,
-            index: tile.index,
-            color: tile.color.unwrap_or_else(|| gen_color(&mut rng)),
+            atlas,
+            index,
+            animation,
+            tint,
+            color: tint.color(colors, rng),
         }
     }
 }
@@ -319,8 +481,12 @@ impl Atlas {
 /// A single entity skin
 #[derive(Default, PartialEq, Clone, Copy)]
 pub struct Skin {
-    atlas_path: &'static str,
+    /// The index of the atlas in the `AtlasList`.
+    atlas: usize,
     index: usize,
+    /// The index of the animated tile in the atlas, if any.
+    animation: Option<usize>,
+    tint: Tint,
     color: Color,
 }
 
@@ -329,7 +495,7 @@ impl Skin {
         &self,
         assets: &SpritePackAssets,
         size: Vec2,
-    ) -> (TextureAtlasSprite, Handle<TextureAtlas>) {
+    ) -> (TextureAtlasSprite, Handle<TextureAtlas>, SpriteAnimation) {
         (
             TextureAtlasSprite {
                 color: self.color,
@@ -337,13 +503,8 @@ impl Skin {
                 custom_size: Some(size),
                 ..default()
             },
-            assets
-                .field(self.atlas_path)
-                .unwrap()
-                .as_any()
-                .downcast_ref::<Handle<TextureAtlas>>()
-                .unwrap()
-                .clone(),
+            assets.atlas(self.atlas),
+            SpriteAnimation(self.animation.map(|tile| (self.atlas, tile))),
         )
     }
 }
@@ -360,13 +521,17 @@ impl Default for SkinSet {
             sprite_pack: default(),
             skins: vec![
                 Skin {
-                    atlas_path: "none",
+                    atlas: 0,
                     index: 0,
+                    animation: None,
+                    tint: Tint::Random,
                     color: Color::WHITE,
                 },
                 Skin {
-                    atlas_path: "none",
+                    atlas: 0,
                     index: 0,
+                    animation: None,
+                    tint: Tint::Random,
                     color: Color::BLACK,
                 },
             ],
@@ -378,6 +543,7 @@ impl SkinSet {
     pub fn new(
         atlas_list: &AtlasList,
         sprite_pack: SpritePack,
+        colors: &[Color],
         count: usize,
         mut rng: impl Rng,
     ) -> Self {
@@ -386,17 +552,24 @@ impl SkinSet {
             skins: Vec::with_capacity(count),
         };
         for _ in 0..count {
-            this.add_skin(atlas_list, &mut rng);
+            this.add_skin(atlas_list, colors, &mut rng);
         }
         this
     }
 
     /// Returns false if it fails to add a non-duplicate skin
-    pub fn add_skin(&mut self, atlas_list: &AtlasList, mut rng: impl Rng) -> bool {
+    pub fn add_skin(
+        &mut self,
+        atlas_list: &AtlasList,
+        colors: &[Color],
+        mut rng: impl Rng,
+    ) -> bool {
         // Make a reasonable attempt to prevent duplicates
         const MAX_ATTEMPTS: usize = 64;
         for _ in 0..MAX_ATTEMPTS {
-            let skin = self.sprite_pack.random(atlas_list, &mut rng);
+            let Some(skin) = self.sprite_pack.random(atlas_list, colors, &mut rng) else {
+                return false;
+            };
             if !self.skins.contains(&skin) {
                 self.skins.push(skin);
                 return true;
@@ -411,24 +584,82 @@ impl SkinSet {
         assets: &SpritePackAssets,
         size: Vec2,
         mut rng: impl Rng,
-    ) -> (TextureAtlasSprite, Handle<TextureAtlas>) {
+    ) -> (TextureAtlasSprite, Handle<TextureAtlas>, SpriteAnimation) {
         self.skins
             .choose(&mut rng)
             .map(|skin| skin.bundle(assets, size))
             .unwrap_or_default()
     }
 
+    pub fn replace_sprite_pack(
+        &mut self,
+        atlas_list: &AtlasList,
+        sprite_pack: SpritePack,
+        colors: &[Color],
+        rng: impl Rng,
+    ) {
+        *self = SkinSet::new(atlas_list, sprite_pack, colors, self.skins.len(), rng);
+    }
+
+    /// Picks new colors for the randomly-tinted skins.
+    pub fn recolor(&mut self, colors: &[Color], mut rng: impl Rng) {
+        for skin in &mut self.skins {
+            if skin.tint == Tint::Random {
+                skin.color = skin.tint.color(colors, &mut rng);
+            }
+        }
+    }
+}
+
+impl Simulation {
+    /// Adds a skin from the current sprite pack, colored from `entity_colors`.
+    pub fn add_skin(&mut self, atlas_list: &AtlasList, rng: impl Rng) -> bool {
+        self.skin_set.add_skin(atlas_list, &self.entity_colors, rng)
+    }
+
+    /// Replaces the skins with skins from another sprite pack, colored from `entity_colors`.
     pub fn replace_sprite_pack(
         &mut self,
         atlas_list: &AtlasList,
         sprite_pack: SpritePack,
         rng: impl Rng,
     ) {
-        *self = SkinSet::new(atlas_list, sprite_pack, self.skins.len(), rng);
+        self.skin_set
+            .replace_sprite_pack(atlas_list, sprite_pack, &self.entity_colors, rng);
+    }
+
+    /// Switches to a new color palette and recolors the skins with it.
+    pub fn set_entity_colors(&mut self, colors: Vec<Color>, rng: impl Rng) {
+        self.entity_colors = colors;
+        self.skin_set.recolor(&self.entity_colors, rng);
     }
 }
 
-/// Sent when the skin set has changed and should be re-applied
+/// The named color palettes that entities can be recolored with.
+#[derive(Default, Reflect, Serialize, Deserialize)]
+pub struct PaletteConfig {
+    palettes: Vec<Palette>,
+}
+
+#[derive(Default, Reflect, Serialize, Deserialize)]
+struct Palette {
+    name: String,
+    colors: Vec<Color>,
+}
+
+impl PaletteConfig {
+    /// Returns the colors of the palette with the given name.
+    pub fn colors(&self, name: &str) -> Vec<Color> {
+        let Some(palette) = self.palettes.iter().find(|palette| palette.name == name) else {
+            error!("Missing the {name} color palette");
+            return vec![];
+        };
+        palette.colors.clone()
+    }
+}
+
+/// Sent when the skin set has changed (e.g. after switching sprite packs or recoloring) and should
+/// be re-applied to the existing entities
 #[derive(Event, Reflect)]
 pub struct SpritePackEvent;
 
@@ -437,10 +668,11 @@ fn apply_sprite_pack(
     root: Res<AppRoot>,
     simulation: Res<Simulation>,
     assets: Res<SpritePackAssets>,
+    mut rng: ResMut<RunRng>,
     children_query: Query<&Children>,
     sprite_query: Query<&TextureAtlasSprite>,
 ) {
-    let mut rng = thread_rng();
+    let rng = &mut rng.skins;
 
     for &entity in children_query.get(root.world).ok().into_iter().flatten() {
         let size = sprite_query
@@ -453,6 +685,59 @@ fn apply_sprite_pack(
 
         commands
             .entity(entity)
-            .insert(simulation.skin_set.bundle(&assets, size, &mut rng));
+            .insert(simulation.skin_set.bundle(&assets, size, &mut *rng));
+    }
+}
+
+/// The animated tile shown by an entity's sprite, as the indices of its atlas in the `AtlasList`
+/// and of the tile in the atlas.
+#[derive(Component, Reflect, Default, Copy, Clone)]
+#[reflect(Component)]
+pub struct SpriteAnimation(Option<(usize, usize)>);
+
+/// The golden ratio conjugate, which spreads consecutive entities' phases evenly.
+const PHASE_STEP: f64 = 0.618_033_988_749_895;
+
+fn animate_sprites(
+    time: Res<GameTime>,
+    simulation: Res<Simulation>,
+    atlas_list: Res<AtlasList>,
+    mut sprite_query: Query<(
+        Entity,
+        &SpriteAnimation,
+        &ViewVisibility,
+        &mut TextureAtlasSprite,
+    )>,
+) {
+    if !simulation.animated_sprites {
+        return;
     }
+
+    let elapsed = time.elapsed_seconds_f64();
+    sprite_query
+        .par_iter_mut()
+        .for_each(|(entity, animation, visibility, mut sprite)| {
+            let Some((atlas, tile)) = animation.0 else {
+                return;
+            };
+            if !visibility.get() {
+                return;
+            }
+            let Some(Tile::Animated { frames, fps, .. }) = atlas_list
+                .atlases
+                .get(atlas)
+                .and_then(|atlas| atlas.tiles.get(tile))
+            else {
+                return;
+            };
+
+            // Stagger the phase per entity so that entities with the same skin don't animate in
+            // lockstep
+            let phase = (entity.index() as f64 * PHASE_STEP).fract();
+            let frame = (elapsed * fps + phase * frames.len() as f64) as usize % frames.len();
+            // Avoid triggering change detection when the frame doesn't change
+            if sprite.index != frames[frame] {
+                sprite.index = frames[frame];
+            }
+        });
 }
@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::AppSet;
+
+pub struct TypingSpeedPlugin;
+
+impl Plugin for TypingSpeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TypingSpeed>()
+            .init_resource::<TypingSpeed>()
+            .add_systems(Update, update_typing_speed.in_set(AppSet::Update));
+    }
+}
+
+/// The number of seconds of recent key presses that the typing speed is measured over.
+const WINDOW: f64 = 5.0;
+
+/// The number of characters in a "word", as in the standard definition of WPM.
+const CHARS_PER_WORD: f64 = 5.0;
+
+/// The WPM at which typing speed bonuses start.
+pub const BASE_WPM: f64 = 40.0;
+
+/// The rolling typing speed of the player, measured in real time.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct TypingSpeed {
+    /// The real time of each recent key press.
+    key_times: VecDeque<f64>,
+    pub keys_per_second: f64,
+    pub wpm: f64,
+}
+
+impl TypingSpeed {
+    pub fn record_keys(&mut self, now: f64, count: usize) {
+        self.key_times.extend(std::iter::repeat_n(now, count));
+    }
+
+    /// The multiplier to lines typed when typing faster than `BASE_WPM`.
+    pub fn bonus(&self) -> f64 {
+        (self.wpm / BASE_WPM).max(1.0)
+    }
+}
+
+fn update_typing_speed(time: Res<Time<Real>>, mut speed: ResMut<TypingSpeed>) {
+    let now = time.elapsed_seconds_f64();
+    while speed.key_times.front().is_some_and(|&t| now - t > WINDOW) {
+        speed.key_times.pop_front();
+    }
+
+    let keys_per_second = speed.key_times.len() as f64 / WINDOW;
+    if speed.keys_per_second != keys_per_second {
+        speed.keys_per_second = keys_per_second;
+        speed.wpm = keys_per_second * 60.0 / CHARS_PER_WORD;
+    }
+}
@@ -1,5 +1,7 @@
+pub mod collection_screen;
 pub mod editor_screen;
 pub mod loading_screen;
+pub mod past_jams_screen;
 pub mod results_screen;
 pub mod splash_screen;
 pub mod title_screen;
@@ -17,6 +19,8 @@ impl Plugin for StatePlugin {
             loading_screen::LoadingScreenStatePlugin,
             editor_screen::EditorScreenStatePlugin,
             results_screen::ResultsScreenStatePlugin,
+            past_jams_screen::PastJamsScreenStatePlugin,
+            collection_screen::CollectionScreenStatePlugin,
         ));
     }
 }
@@ -30,4 +34,6 @@ pub enum AppState {
     LoadingScreen,
     EditorScreen,
     ResultsScreen,
+    PastJamsScreen,
+    CollectionScreen,
 }
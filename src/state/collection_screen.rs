@@ -0,0 +1,423 @@
+use bevy::prelude::*;
+use bevy::ui::Val::*;
+use bevy_mod_picking::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::collection::SkinCollection;
+use crate::config::Config;
+use crate::simulation::AtlasList;
+use crate::simulation::SpritePackAssets;
+use crate::state::AppState;
+use crate::state::AppState::*;
+use crate::ui::FontSize;
+use crate::ui::InteractionPalette;
+use crate::ui::ScrollContent;
+use crate::ui::Tooltip;
+use crate::ui::TooltipSide;
+use crate::ui::BOLD_FONT_HANDLE;
+use crate::ui::FONT_HANDLE;
+use crate::ui::HEADER_FONT_HANDLE;
+use crate::util::pretty_num;
+use crate::AppRoot;
+
+pub struct CollectionScreenStatePlugin;
+
+impl Plugin for CollectionScreenStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CollectionScreenConfig>()
+            .add_systems(OnEnter(CollectionScreen), enter_collection_screen)
+            .add_systems(OnExit(CollectionScreen), exit_collection_screen);
+    }
+}
+
+#[derive(Default, Reflect, Serialize, Deserialize)]
+pub struct CollectionScreenConfig {
+    background_color: Color,
+    border_color: Color,
+    border_width: Val,
+    text_color: Color,
+    highlight_text_color: Color,
+    font_size: Val,
+
+    title_text_color: Color,
+    title_font_size: Val,
+
+    pack_header_background_color: Color,
+    pack_header_text_color: Color,
+    pack_header_font_size: Val,
+    sheet_font_size: Val,
+
+    icon_size: Val,
+    icon_gap: Val,
+    /// The tint of skins that haven't been discovered yet.
+    undiscovered_color: Color,
+
+    return_button_normal_color: Color,
+    return_button_hovered_color: Color,
+    return_button_pressed_color: Color,
+    return_button_text_color: Color,
+}
+
+const TITLE_TEXT: &str = "Collection";
+
+fn enter_collection_screen(
+    mut commands: Commands,
+    root: Res<AppRoot>,
+    config: Res<Config>,
+    atlas_list: Res<AtlasList>,
+    assets: Res<SpritePackAssets>,
+    collection: Res<SkinCollection>,
+) {
+    let bonus = collection.presentation_bonus(&atlas_list, &config.skin_collection);
+    let config = &config.collection_screen;
+    commands.insert_resource(ClearColor(config.background_color));
+
+    let screen = commands
+        .spawn((
+            Name::new("CollectionScreen"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    align_items: AlignItems::Center,
+                    padding: UiRect::new(VMin(8.3), VMin(8.3), Vh(4.0), Vh(4.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: config.background_color.into(),
+                ..default()
+            },
+        ))
+        .set_parent(root.ui)
+        .id();
+
+    commands
+        .spawn((
+            Name::new("TitleText"),
+            TextBundle::from_section(
+                TITLE_TEXT,
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: config.title_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.title_font_size),
+        ))
+        .set_parent(screen);
+
+    let by_tile = collection.by_tile();
+    let total = atlas_list
+        .sprite_packs()
+        .flat_map(|sprite_pack| atlas_list.atlases(sprite_pack))
+        .map(|atlas| atlas_list.tile_keys(atlas).count())
+        .sum::<usize>();
+    let (completed_atlases, completed_packs) = collection.completed_sets(&atlas_list);
+    commands
+        .spawn((
+            Name::new("SummaryText"),
+            TextBundle::from_section(
+                format!(
+                    "{} of {total} skins discovered. \
+                     Completed {completed_atlases} sprite sheets and {completed_packs} sprite packs \
+                     for +{} Presentation in every jam.",
+                    by_tile.len(),
+                    pretty_num(bonus),
+                ),
+                TextStyle {
+                    font: FONT_HANDLE,
+                    color: config.text_color,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                margin: UiRect::vertical(Vh(3.0)),
+                ..default()
+            }),
+            FontSize::new(config.font_size),
+        ))
+        .set_parent(screen);
+
+    let list_panel = commands
+        .spawn((
+            Name::new("CollectionPanel"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_grow: 1.0,
+                    border: UiRect::all(config.border_width),
+                    ..default()
+                },
+                border_color: config.border_color.into(),
+                ..default()
+            },
+        ))
+        .set_parent(screen)
+        .id();
+
+    let scroll_view = commands
+        .spawn((
+            Name::new("CollectionScrollView"),
+            NodeBundle {
+                style: Style {
+                    // Restrict height to the available space in the parent node
+                    position_type: PositionType::Absolute,
+                    overflow: Overflow::clip_y(),
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    padding: UiRect::right(Px(12.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .set_parent(list_panel)
+        .id();
+
+    let scrollbar = commands
+        .spawn((
+            Name::new("CollectionScrollbar"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::ZERO,
+                    width: Px(8.0),
+                    height: Percent(100.0),
+                    ..default()
+                },
+                background_color: config.pack_header_background_color.into(),
+                ..default()
+            },
+        ))
+        .set_parent(list_panel)
+        .id();
+
+    let list = commands
+        .spawn((
+            Name::new("CollectionList"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            ScrollContent {
+                position: 0.0,
+                sensitivity: 1.5,
+                scrollbar,
+            },
+        ))
+        .set_parent(scroll_view)
+        .id();
+
+    // List every skin, grouped by sprite pack and then sprite sheet
+    for sprite_pack in atlas_list.sprite_packs() {
+        let atlases = atlas_list.atlases(sprite_pack);
+        let pack_total = atlases
+            .clone()
+            .map(|atlas| atlas_list.tile_keys(atlas).count())
+            .sum::<usize>();
+        let pack_discovered = atlases
+            .clone()
+            .flat_map(|atlas| atlas_list.tile_keys(atlas))
+            .filter(|tile| by_tile.contains_key(tile))
+            .count();
+
+        let header = commands
+            .spawn((
+                Name::new("PackHeader"),
+                NodeBundle {
+                    style: Style {
+                        width: Percent(100.0),
+                        padding: UiRect::axes(VMin(2.0), VMin(0.8)),
+                        border: UiRect::bottom(config.border_width),
+                        ..default()
+                    },
+                    background_color: config.pack_header_background_color.into(),
+                    border_color: config.border_color.into(),
+                    ..default()
+                },
+            ))
+            .set_parent(list)
+            .id();
+        commands
+            .spawn((
+                Name::new("PackHeaderText"),
+                TextBundle::from_section(
+                    format!(
+                        "{} ({pack_discovered}/{pack_total})",
+                        atlas_list.label(sprite_pack),
+                    ),
+                    TextStyle {
+                        font: BOLD_FONT_HANDLE,
+                        color: if pack_discovered == pack_total {
+                            config.highlight_text_color
+                        } else {
+                            config.pack_header_text_color
+                        },
+                        ..default()
+                    },
+                ),
+                FontSize::new(config.pack_header_font_size),
+            ))
+            .set_parent(header);
+
+        for atlas in atlases {
+            let tiles = atlas_list.tile_keys(atlas).collect::<Vec<_>>();
+            let discovered = tiles
+                .iter()
+                .filter(|tile| by_tile.contains_key(tile))
+                .count();
+
+            commands
+                .spawn((
+                    Name::new("SheetText"),
+                    TextBundle::from_section(
+                        format!(
+                            "{} ({discovered}/{})",
+                            atlas_list.atlas_label(atlas),
+                            tiles.len(),
+                        ),
+                        TextStyle {
+                            font: FONT_HANDLE,
+                            color: if discovered == tiles.len() {
+                                config.highlight_text_color
+                            } else {
+                                config.text_color
+                            },
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::new(VMin(2.0), VMin(2.0), VMin(1.5), VMin(0.8)),
+                        ..default()
+                    }),
+                    FontSize::new(config.sheet_font_size),
+                ))
+                .set_parent(list);
+
+            let grid = commands
+                .spawn((
+                    Name::new("SkinGrid"),
+                    NodeBundle {
+                        style: Style {
+                            width: Percent(100.0),
+                            padding: UiRect::horizontal(VMin(2.0)),
+                            flex_wrap: FlexWrap::Wrap,
+                            column_gap: config.icon_gap,
+                            row_gap: config.icon_gap,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ))
+                .set_parent(list)
+                .id();
+
+            for tile in tiles {
+                let record = by_tile.get(&tile);
+                let icon = commands
+                    .spawn((
+                        Name::new("SkinIcon"),
+                        AtlasImageBundle {
+                            style: Style {
+                                width: config.icon_size,
+                                height: config.icon_size,
+                                ..default()
+                            },
+                            background_color: if record.is_some() {
+                                Color::WHITE
+                            } else {
+                                config.undiscovered_color
+                            }
+                            .into(),
+                            texture_atlas: assets.atlas(atlas),
+                            texture_atlas_image: UiTextureAtlasImage {
+                                index: tile.index,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                    ))
+                    .set_parent(grid)
+                    .id();
+
+                let Some(record) = record else {
+                    continue;
+                };
+                commands.entity(icon).insert((
+                    Interaction::default(),
+                    Tooltip {
+                        text: format!(
+                            "{} #{}{}\nDiscovered in {} {}\nFirst seen {}",
+                            atlas_list.atlas_label(atlas),
+                            tile.index,
+                            if tile.animated { " (animated)" } else { "" },
+                            record.count,
+                            if record.count == 1 { "run" } else { "runs" },
+                            record.date(),
+                        ),
+                        side: TooltipSide::Top,
+                        offset: Vec2::ZERO,
+                    },
+                ));
+            }
+        }
+    }
+
+    let return_button = spawn_return_button(&mut commands, config);
+    commands.entity(return_button).set_parent(screen);
+}
+
+fn exit_collection_screen(mut commands: Commands, root: Res<AppRoot>) {
+    commands.entity(root.ui).despawn_descendants();
+}
+
+fn spawn_return_button(commands: &mut Commands, config: &CollectionScreenConfig) -> Entity {
+    let return_button = commands
+        .spawn((
+            Name::new("ReturnButton"),
+            ButtonBundle {
+                style: Style {
+                    margin: UiRect::top(Vh(4.0)),
+                    padding: UiRect::all(Px(16.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: config.return_button_normal_color.into(),
+                ..default()
+            },
+            InteractionPalette {
+                normal: config.return_button_normal_color,
+                hovered: config.return_button_hovered_color,
+                pressed: config.return_button_pressed_color,
+                disabled: Color::NONE,
+            },
+            On::<Pointer<Click>>::run(|mut next_state: ResMut<NextState<_>>| {
+                next_state.set(AppState::TitleScreen);
+            }),
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("ReturnButtonText"),
+            TextBundle::from_section(
+                "Back",
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: config.return_button_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.font_size),
+        ))
+        .set_parent(return_button);
+
+    return_button
+}
@@ -1,6 +1,10 @@
+mod chart_panel;
+mod code_gen;
 mod code_panel;
 mod info_bar;
+mod jam_clock;
 mod outline_panel;
+mod sandbox_panel;
 mod scene_view;
 mod upgrade_panel;
 
@@ -12,17 +16,30 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::config::Config;
+use crate::physics::PhysicsSettings;
+use crate::simulation::CompilerErrors;
+use crate::simulation::GameMode;
+use crate::simulation::GameTime;
+use crate::simulation::PassiveCodeTyper;
+use crate::simulation::PassiveEntitySpawner;
+use crate::simulation::RunStats;
+use crate::simulation::Simulation;
+use crate::simulation::TypingSpeed;
+use crate::state::editor_screen::chart_panel::spawn_chart_panel;
 pub use crate::state::editor_screen::code_panel::spawn_code_panel;
 use crate::state::editor_screen::code_panel::spawn_light_code_panel;
 use crate::state::editor_screen::info_bar::spawn_info_bar;
+pub use crate::state::editor_screen::jam_clock::JamClock;
 use crate::state::editor_screen::outline_panel::spawn_outline_panel;
 pub use crate::state::editor_screen::outline_panel::UpgradeOutline;
+use crate::state::editor_screen::sandbox_panel::spawn_sandbox_panel;
 use crate::state::editor_screen::scene_view::spawn_scene_view;
 pub use crate::state::editor_screen::scene_view::SceneView;
 pub use crate::state::editor_screen::scene_view::SceneViewBounds;
 pub use crate::state::editor_screen::scene_view::WrapWithinSceneView;
 use crate::state::editor_screen::upgrade_panel::spawn_upgrade_panel;
 use crate::state::AppState::*;
+use crate::ui::CodeTabs;
 use crate::AppRoot;
 
 pub struct EditorScreenStatePlugin;
@@ -34,9 +51,22 @@ impl Plugin for EditorScreenStatePlugin {
             .init_collection::<EditorScreenAssets>()
             .add_systems(OnEnter(EditorScreen), enter_editor_screen)
             .add_systems(OnExit(EditorScreen), exit_editor_screen)
+            .add_systems(OnExit(ResultsScreen), reset_run)
+            .add_systems(
+                OnTransition {
+                    from: EditorScreen,
+                    to: TitleScreen,
+                },
+                reset_run,
+            )
             .add_plugins((
+                chart_panel::ChartPanelPlugin,
+                code_gen::CodeGenPlugin,
+                code_panel::CodePanelPlugin,
                 info_bar::InfoBarPlugin,
+                jam_clock::JamClockPlugin,
                 outline_panel::OutlinePanelPlugin,
+                sandbox_panel::SandboxPanelPlugin,
                 scene_view::SceneViewPlugin,
                 upgrade_panel::UpgradePanelPlugin,
             ));
@@ -54,6 +84,20 @@ pub struct EditorScreenTheme {
 
     code_panel_background_color: Color,
     code_panel_text_color: Color,
+    code_panel_keyword_color: Color,
+    code_panel_string_color: Color,
+    code_panel_comment_color: Color,
+    code_panel_number_color: Color,
+    code_panel_macro_color: Color,
+    code_panel_lifetime_color: Color,
+    code_panel_ghost_color: Color,
+    code_panel_typo_color: Color,
+
+    chart_panel_background_color: Color,
+    chart_lines_color: Color,
+    chart_entities_color: Color,
+    chart_tech_debt_color: Color,
+    chart_marker_color: Color,
 
     upgrade_panel_background_color: Color,
     upgrade_panel_text_color: Color,
@@ -71,6 +115,9 @@ pub struct EditorScreenTheme {
 pub struct EditorScreenConfig {
     scene_view_background_color: Color,
 
+    jam_clock_days: f64,
+    jam_clock_day_duration: f64,
+
     info_bar_height: Val,
     info_bar_font_size: Val,
 
@@ -80,11 +127,16 @@ pub struct EditorScreenConfig {
 
     code_panel_height: Val,
     code_panel_font_size: Val,
-    code_panel_lines_max: usize,
+    code_panel_history_max: usize,
+
+    chart_panel_height: Val,
+    chart_panel_font_size: Val,
 
     upgrade_panel_width: Val,
     upgrade_panel_header_font_size: Val,
 
+    sandbox_panel_width: Val,
+
     upgrade_button_height: Val,
     upgrade_button_font_size: Val,
 
@@ -120,13 +172,14 @@ fn enter_editor_screen(
     mut commands: Commands,
     root: Res<AppRoot>,
     config: Res<Config>,
-    time: Res<Time>,
+    time: Res<GameTime>,
+    mode: Res<GameMode>,
 ) {
     let config = &config.editor_screen;
     commands.insert_resource(ClearColor(config.scene_view_background_color));
     commands.insert_resource(EditorScreenStartTime(time.elapsed_seconds_f64()));
 
-    let screen = spawn_editor_screen(&mut commands, config, &config.light_theme, true);
+    let screen = spawn_editor_screen(&mut commands, config, &config.light_theme, true, *mode);
     commands.entity(screen).set_parent(root.ui);
 }
 
@@ -135,6 +188,7 @@ pub fn spawn_editor_screen(
     config: &EditorScreenConfig,
     theme: &EditorScreenTheme,
     light_mode: bool,
+    mode: GameMode,
 ) -> Entity {
     let editor_screen = commands
         .spawn((
@@ -191,6 +245,9 @@ pub fn spawn_editor_screen(
     let scene_view = spawn_scene_view(commands);
     commands.entity(scene_view).set_parent(vbox);
 
+    let chart_panel = spawn_chart_panel(commands, config, theme);
+    commands.entity(chart_panel).set_parent(vbox);
+
     let code_panel = if light_mode {
         spawn_light_code_panel(commands, config, theme)
     } else {
@@ -198,9 +255,14 @@ pub fn spawn_editor_screen(
     };
     commands.entity(code_panel).set_parent(vbox);
 
-    let upgrade_panel = spawn_upgrade_panel(commands, config, theme);
+    let upgrade_panel = spawn_upgrade_panel(commands, config, theme, mode);
     commands.entity(upgrade_panel).set_parent(hbox);
 
+    if mode == GameMode::Sandbox {
+        let sandbox_panel = spawn_sandbox_panel(commands, config, theme);
+        commands.entity(sandbox_panel).set_parent(editor_screen);
+    }
+
     commands.insert_resource(ActiveEditorTheme(theme.clone()));
 
     editor_screen
@@ -219,3 +281,17 @@ fn exit_editor_screen(
     };
     transform.translation = Vec2::ZERO.extend(transform.translation.z);
 }
+
+/// Resets the resources of the finished run so replaying works.
+fn reset_run(mut commands: Commands) {
+    commands.insert_resource(Simulation::default());
+    commands.insert_resource(GameTime::default());
+    commands.insert_resource(RunStats::default());
+    commands.insert_resource(TypingSpeed::default());
+    commands.insert_resource(PhysicsSettings::default());
+    commands.insert_resource(PassiveCodeTyper::default());
+    commands.insert_resource(PassiveEntitySpawner::default());
+    commands.insert_resource(UpgradeOutline::default());
+    commands.insert_resource(CodeTabs::default());
+    commands.insert_resource(CompilerErrors::default());
+}
@@ -0,0 +1,372 @@
+use bevy::prelude::*;
+use bevy::ui::Val::*;
+use bevy_mod_picking::prelude::*;
+use strum::EnumIter;
+use strum::IntoEnumIterator;
+
+use crate::simulation::RunStats;
+use crate::simulation::RunStatsSample;
+use crate::state::editor_screen::EditorScreenConfig;
+use crate::state::editor_screen::EditorScreenTheme;
+use crate::state::AppState;
+use crate::ui::FontSize;
+use crate::ui::FONT_HANDLE;
+use crate::ui::HEADER_FONT_HANDLE;
+use crate::util::pretty_num;
+use crate::AppSet;
+
+pub struct ChartPanelPlugin;
+
+impl Plugin for ChartPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ChartBody>()
+            .register_type::<ChartPlot>()
+            .register_type::<ChartPoint>()
+            .register_type::<ChartMarkers>()
+            .register_type::<ChartMarker>()
+            .register_type::<ChartLegendText>()
+            .add_systems(
+                Update,
+                update_chart
+                    .in_set(AppSet::Update)
+                    .run_if(in_state(AppState::EditorScreen)),
+            );
+    }
+}
+
+/// The number of points drawn for each series. Samples are skipped to fit.
+const POINTS_PER_SERIES: usize = 96;
+
+#[derive(Reflect, Clone, Copy, EnumIter)]
+enum ChartSeries {
+    Lines,
+    Entities,
+    TechDebt,
+}
+
+impl ChartSeries {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Lines => "lines",
+            Self::Entities => "entities",
+            Self::TechDebt => "tech debt",
+        }
+    }
+
+    fn value(self, sample: &RunStatsSample) -> f64 {
+        match self {
+            Self::Lines => sample.lines,
+            Self::Entities => sample.entities,
+            Self::TechDebt => sample.tech_debt,
+        }
+    }
+
+    fn color(self, theme: &EditorScreenTheme) -> Color {
+        match self {
+            Self::Lines => theme.chart_lines_color,
+            Self::Entities => theme.chart_entities_color,
+            Self::TechDebt => theme.chart_tech_debt_color,
+        }
+    }
+}
+
+pub fn spawn_chart_panel(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+) -> Entity {
+    let chart_panel = commands
+        .spawn((
+            Name::new("ChartPanel"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    padding: UiRect::axes(VMin(2.0), Px(4.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: theme.chart_panel_background_color.into(),
+                ..default()
+            },
+        ))
+        .id();
+
+    let header = commands
+        .spawn((
+            Name::new("ChartHeader"),
+            ButtonBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    align_items: AlignItems::Center,
+                    column_gap: Px(16.0),
+                    ..default()
+                },
+                background_color: Color::NONE.into(),
+                ..default()
+            },
+            On::<Pointer<Click>>::run(toggle_chart_body),
+        ))
+        .set_parent(chart_panel)
+        .id();
+
+    commands
+        .spawn((
+            Name::new("ChartHeaderText"),
+            TextBundle::from_section(
+                "Stats",
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: theme.code_panel_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.chart_panel_font_size),
+        ))
+        .set_parent(header);
+
+    commands
+        .spawn((
+            Name::new("ChartLegendText"),
+            TextBundle::from_sections(ChartSeries::iter().map(|series| {
+                TextSection::new(
+                    "",
+                    TextStyle {
+                        font: FONT_HANDLE,
+                        color: series.color(theme),
+                        ..default()
+                    },
+                )
+            })),
+            FontSize::new(config.chart_panel_font_size),
+            ChartLegendText,
+        ))
+        .set_parent(header);
+
+    let body = commands
+        .spawn((
+            Name::new("ChartBody"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: config.chart_panel_height,
+                    padding: UiRect::vertical(Px(4.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            ChartBody,
+        ))
+        .set_parent(chart_panel)
+        .id();
+
+    let plot = commands
+        .spawn((
+            Name::new("ChartPlot"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    ..default()
+                },
+                ..default()
+            },
+            ChartPlot,
+        ))
+        .set_parent(body)
+        .id();
+
+    commands
+        .spawn((
+            Name::new("ChartMarkers"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    ..default()
+                },
+                ..default()
+            },
+            ChartMarkers {
+                color: theme.chart_marker_color,
+                spawned: 0,
+            },
+        ))
+        .set_parent(plot);
+
+    for series in ChartSeries::iter() {
+        for index in 0..POINTS_PER_SERIES {
+            commands
+                .spawn((
+                    Name::new("ChartPoint"),
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            width: Px(3.0),
+                            height: Px(3.0),
+                            ..default()
+                        },
+                        background_color: series.color(theme).into(),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    ChartPoint { series, index },
+                ))
+                .set_parent(plot);
+        }
+    }
+
+    chart_panel
+}
+
+#[derive(Component, Reflect)]
+struct ChartBody;
+
+fn toggle_chart_body(mut body_query: Query<&mut Style, With<ChartBody>>) {
+    for mut style in &mut body_query {
+        style.display = match style.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+#[derive(Component, Reflect)]
+struct ChartPlot;
+
+#[derive(Component, Reflect)]
+struct ChartPoint {
+    series: ChartSeries,
+    index: usize,
+}
+
+/// The node that upgrade markers are spawned in, as upgrades are installed.
+#[derive(Component, Reflect)]
+struct ChartMarkers {
+    color: Color,
+    /// The number of markers spawned so far.
+    spawned: usize,
+}
+
+/// A vertical line marking when an upgrade was installed.
+#[derive(Component, Reflect)]
+struct ChartMarker {
+    /// Seconds of game time since the start of the run.
+    time: f64,
+}
+
+#[derive(Component, Reflect)]
+struct ChartLegendText;
+
+/// Maps a value onto [0, 1] on a log scale, relative to the given max.
+fn log_scale(x: f64, max: f64) -> f64 {
+    if max <= 0.0 {
+        return 0.0;
+    }
+    ((x.max(0.0) + 1.0).log10() / (max + 1.0).log10()).clamp(0.0, 1.0)
+}
+
+fn update_chart(
+    mut commands: Commands,
+    stats: Res<RunStats>,
+    mut last_sample_count: Local<usize>,
+    new_plot_query: Query<(), Added<ChartPlot>>,
+    body_query: Query<&Style, (With<ChartBody>, Without<ChartPoint>, Without<ChartMarker>)>,
+    mut point_query: Query<(&ChartPoint, &mut Style, &mut Visibility), Without<ChartMarker>>,
+    mut markers_query: Query<(Entity, &mut ChartMarkers)>,
+    mut marker_query: Query<(&ChartMarker, &mut Style)>,
+    mut legend_query: Query<&mut Text, With<ChartLegendText>>,
+) {
+    let samples = &stats.samples;
+    if samples.len() == *last_sample_count && new_plot_query.is_empty() {
+        return;
+    }
+    // Skip while the chart is collapsed, catching up once it is shown again
+    if body_query
+        .iter()
+        .all(|style| style.display == Display::None)
+    {
+        return;
+    }
+    *last_sample_count = samples.len();
+    let Some(latest) = samples.last() else {
+        return;
+    };
+
+    // Update legend
+    for mut text in &mut legend_query {
+        for (section, series) in text.sections.iter_mut().zip(ChartSeries::iter()) {
+            section.value = format!(
+                "{} {}  ",
+                pretty_num(series.value(latest).floor()),
+                series.label(),
+            );
+        }
+    }
+
+    // Update points
+    let end_time = latest.time.max(f64::EPSILON);
+    let maxes = ChartSeries::iter()
+        .map(|series| {
+            samples
+                .iter()
+                .map(|sample| series.value(sample))
+                .fold(0.0, f64::max)
+        })
+        .collect::<Vec<_>>();
+    let point_count = samples.len().min(POINTS_PER_SERIES);
+    for (point, mut style, mut visibility) in &mut point_query {
+        if point.index >= point_count {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        // Spread the points evenly over the samples, always including the latest one
+        let sample_idx = if point_count <= 1 {
+            samples.len() - 1
+        } else {
+            point.index * (samples.len() - 1) / (point_count - 1)
+        };
+        let sample = &samples[sample_idx];
+        let x = sample.time / end_time;
+        let y = log_scale(point.series.value(sample), maxes[point.series as usize]);
+
+        *visibility = Visibility::Inherited;
+        style.left = Percent(100.0 * x as f32);
+        style.bottom = Percent(100.0 * y as f32);
+    }
+
+    // Move the existing upgrade markers
+    let marker_left = |time: f64| Percent(100.0 * (time / end_time).min(1.0) as f32);
+    for (marker, mut style) in &mut marker_query {
+        style.left = marker_left(marker.time);
+    }
+
+    // Spawn markers for the newly installed upgrades
+    for (entity, mut markers) in &mut markers_query {
+        if markers.spawned > stats.upgrades.len() {
+            commands.entity(entity).despawn_descendants();
+            markers.spawned = 0;
+        }
+        for &(time, _) in &stats.upgrades[markers.spawned..] {
+            commands
+                .spawn((
+                    Name::new("ChartMarker"),
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            left: marker_left(time),
+                            width: Px(1.0),
+                            height: Percent(100.0),
+                            ..default()
+                        },
+                        background_color: markers.color.into(),
+                        ..default()
+                    },
+                    ChartMarker { time },
+                ))
+                .set_parent(entity);
+        }
+        markers.spawned = stats.upgrades.len();
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::state::editor_screen::UpgradeOutline;
+use crate::ui::CodeTyper;
+use crate::upgrade::UpgradeList;
+use crate::AppSet;
+
+pub struct CodeGenPlugin;
+
+impl Plugin for CodeGenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_generated_code.in_set(AppSet::Update));
+    }
+}
+
+/// Regenerates the code typed by the code typers whenever an upgrade is installed.
+fn update_generated_code(
+    upgrade_list: Res<UpgradeList>,
+    outline: Res<UpgradeOutline>,
+    mut typer_query: Query<&mut CodeTyper>,
+    new_typer_query: Query<(), Added<CodeTyper>>,
+) {
+    if !outline.is_changed() && new_typer_query.is_empty() {
+        return;
+    }
+
+    let code: Arc<str> = generate_code(&upgrade_list, &outline).into();
+    for mut typer in &mut typer_query {
+        typer.code.set_generated(code.clone());
+    }
+}
+
+/// Synthesizes Bevy code for a game with the installed upgrades: a plugin with a component and a
+/// system for each plugin upgrade, and a startup system spawning named entities for each other
+/// upgrade.
+fn generate_code(upgrade_list: &UpgradeList, outline: &UpgradeOutline) -> String {
+    let mut entries = outline
+        .0
+        .iter()
+        .filter(|(&kind, _)| !upgrade_list[kind].no_outline)
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return String::new();
+    }
+    entries.sort();
+
+    let mut plugins = vec![];
+    let mut systems = vec![];
+    let mut items = String::new();
+    for &(&kind, &count) in &entries {
+        let upgrade = &upgrade_list[kind];
+        let desc = upgrade.description().replace('\n', " ");
+
+        // Plugin upgrades get a plugin with a component and a system
+        if let Some(component) = upgrade
+            .name
+            .strip_suffix("Plugin")
+            .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric()))
+        {
+            let plugin = &upgrade.name;
+            let system = format!("update_{}", snake_case(component));
+            let _ = write!(
+                items,
+                "
+/// {desc}
+pub struct {plugin};
+
+impl Plugin for {plugin} {{
+    fn build(&self, app: &mut App) {{
+        app.register_type::<{component}>()
+            .add_systems(Update, {system});
+    }}
+}}
+
+#[derive(Component, Reflect)]
+struct {component}(usize);
+
+fn {system}(mut query: Query<&mut {component}>) {{
+    for mut {var} in &mut query {{
+        {var}.0 = {count};
+    }}
+}}
+",
+                var = snake_case(component),
+            );
+            plugins.push(plugin.clone());
+        } else {
+            let system = format!(
+                "spawn_{}",
+                snake_case(&upgrade.name).trim_start_matches('_')
+            );
+            let name = format!(
+                "{} {{i}}",
+                upgrade.name.replace('{', "{{").replace('}', "}}"),
+            );
+            let _ = write!(
+                items,
+                "
+/// {desc}
+fn {system}(mut commands: Commands) {{
+    commands.spawn_batch((0..{count}).map(|i| Name::new(format!({name:?}))));
+}}
+",
+            );
+            systems.push(system);
+        }
+    }
+
+    let mut code = format!(
+        "// Generated from {} installed upgrades
+
+use bevy::prelude::*;
+
+fn main() {{
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+",
+        entries.len(),
+    );
+    for plugin in &plugins {
+        let _ = writeln!(code, "            {plugin},");
+    }
+    code.push_str("        ))\n");
+    if !systems.is_empty() {
+        let _ = writeln!(
+            code,
+            "        .add_systems(Startup, ({}))",
+            systems.join(", ")
+        );
+    }
+    code.push_str("        .run();\n}\n");
+    code.push_str(&items);
+    code.push('\n');
+    code
+}
+
+/// Converts a name like "TouchOfLife" or "Dark Mode (Dracula)" to an identifier like
+/// "touch_of_life" or "dark_mode_dracula".
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut prev_lowercase = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            prev_lowercase = false;
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            continue;
+        }
+        if c.is_uppercase() && prev_lowercase {
+            snake.push('_');
+        }
+        if snake.is_empty() && c.is_ascii_digit() {
+            // Identifiers can't start with a digit
+            snake.push('_');
+        }
+        prev_lowercase = c.is_lowercase();
+        snake.extend(c.to_lowercase());
+    }
+    snake.trim_end_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case_names() {
+        assert_eq!(snake_case("TouchOfLife"), "touch_of_life");
+        assert_eq!(snake_case("Dark Mode (Dracula)"), "dark_mode_dracula");
+        assert_eq!(snake_case("10x Dev"), "_10x_dev");
+    }
+}
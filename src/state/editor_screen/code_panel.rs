@@ -1,11 +1,68 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
 use bevy::ui::Val::*;
+use bevy_mod_picking::prelude::*;
 
 use super::EditorScreenConfig;
+use crate::config::Config;
+use crate::simulation::CompilerErrors;
+use crate::state::editor_screen::ActiveEditorTheme;
 use crate::state::editor_screen::EditorScreenTheme;
+use crate::ui::AccuracyMode;
+use crate::ui::CodeTabs;
 use crate::ui::CodeTyper;
 use crate::ui::FontSize;
+use crate::ui::InteractionPalette;
+use crate::ui::ScrollContent;
+use crate::ui::SyntaxColors;
+use crate::ui::SyntaxHighlighter;
+use crate::ui::BOLD_FONT_HANDLE;
 use crate::ui::FONT_HANDLE;
+use crate::upgrade::UpgradeEvent;
+use crate::upgrade::UpgradeList;
+use crate::util::DespawnSet;
+use crate::AppSet;
+
+pub struct CodePanelPlugin;
+
+impl Plugin for CodePanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CodePanelTabs>()
+            .register_type::<CodeTabButton>()
+            .register_type::<CodeTabBody>()
+            .register_type::<IsCodeFileText>()
+            .register_type::<IsAccuracyButtonText>()
+            .register_type::<IsCompilerErrorPanel>()
+            .register_type::<IsCompilerErrorText>()
+            .register_type::<CodeHistory>()
+            .add_systems(Update, follow_code_history.in_set(AppSet::Tick))
+            .add_systems(
+                Update,
+                (
+                    (
+                        open_code_tabs.run_if(on_event::<UpgradeEvent>()),
+                        spawn_code_tabs,
+                    )
+                        .chain(),
+                    record_code_tab_lines,
+                    update_code_history,
+                    update_code_history_follow,
+                )
+                    .in_set(AppSet::Update),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_code_tab_focus,
+                    update_code_file_text,
+                    update_accuracy_button_text,
+                    update_compiler_error_panel,
+                )
+                    .in_set(AppSet::End),
+            );
+    }
+}
 
 const LIGHT_THEME_TEXT: &str = "Aargh, my eyes!
 
@@ -13,6 +70,48 @@ I should pick a dark theme from the panel on the right.
 
 Installed upgrades will appear on the left.";
 
+/// The code panel that tabs are spawned in, as they are opened.
+#[derive(Component, Reflect)]
+struct CodePanelTabs {
+    /// The node that the tab buttons are spawned in.
+    tab_list: Entity,
+    /// The node that the tab bodies are spawned in.
+    bodies: Entity,
+    /// The number of tabs spawned so far.
+    spawned: usize,
+}
+
+#[derive(Component, Reflect)]
+struct CodeTabButton(usize);
+
+#[derive(Component, Reflect)]
+struct CodeTabBody(usize);
+
+#[derive(Component, Reflect)]
+struct IsCodeFileText;
+
+#[derive(Component, Reflect)]
+struct IsAccuracyButtonText;
+
+#[derive(Component, Reflect)]
+struct IsCompilerErrorPanel;
+
+#[derive(Component, Reflect)]
+struct IsCompilerErrorText;
+
+/// The lines of code that have scrolled past the code typer.
+#[derive(Component, Reflect)]
+struct CodeHistory {
+    /// The code typer whose finished lines are added to the history.
+    typer: Entity,
+    /// The node that the history lines are spawned in.
+    container: Entity,
+    /// The history line entities, oldest first.
+    lines: VecDeque<Entity>,
+    /// Whether to keep the newest line in view.
+    follow: bool,
+}
+
 /// Spawns the fake code panel with light theme.
 pub fn spawn_light_code_panel(
     commands: &mut Commands,
@@ -27,7 +126,7 @@ pub fn spawn_light_code_panel(
                     width: Percent(100.0),
                     height: Val::ZERO,
                     min_height: config.code_panel_height,
-                    padding: UiRect::all(VMin(2.0)),
+                    flex_direction: FlexDirection::Column,
                     ..default()
                 },
                 background_color: theme.code_panel_background_color.into(),
@@ -36,6 +135,15 @@ pub fn spawn_light_code_panel(
         ))
         .id();
 
+    let tab_bar = spawn_code_tab_bar(commands, theme);
+    commands.entity(tab_bar).set_parent(code_panel);
+
+    let tab = spawn_code_tab(commands, config, theme, "README.txt");
+    commands.entity(tab).set_parent(tab_bar);
+
+    let body = spawn_code_panel_body(commands);
+    commands.entity(body).set_parent(code_panel);
+
     commands
         .spawn((
             Name::new("CodePanelText"),
@@ -49,7 +157,7 @@ pub fn spawn_light_code_panel(
             ),
             FontSize::new(config.code_panel_font_size),
         ))
-        .set_parent(code_panel);
+        .set_parent(body);
 
     code_panel
 }
@@ -65,8 +173,9 @@ pub fn spawn_code_panel(
             NodeBundle {
                 style: Style {
                     width: Percent(100.0),
+                    height: config.code_panel_height,
                     min_height: config.code_panel_height,
-                    padding: UiRect::all(VMin(2.0)),
+                    flex_direction: FlexDirection::Column,
                     ..default()
                 },
                 background_color: theme.code_panel_background_color.into(),
@@ -75,26 +184,701 @@ pub fn spawn_code_panel(
         ))
         .id();
 
-    commands
+    let tab_bar = spawn_code_tab_bar(commands, theme);
+    commands.entity(tab_bar).set_parent(code_panel);
+
+    let tab_list = commands
+        .spawn((Name::new("CodeTabList"), NodeBundle::default()))
+        .set_parent(tab_bar)
+        .id();
+
+    let accuracy_button = spawn_accuracy_button(commands, config, theme);
+    commands.entity(accuracy_button).set_parent(tab_bar);
+
+    let file_header = spawn_code_file_header(commands, config, theme);
+    commands.entity(file_header).set_parent(code_panel);
+
+    let bodies = commands
+        .spawn((
+            Name::new("CodeTabBodies"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_grow: 1.0,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .set_parent(code_panel)
+        .id();
+
+    let error_panel = spawn_compiler_error_panel(commands, config, theme);
+    commands.entity(error_panel).set_parent(code_panel);
+
+    // The tabs themselves are spawned by `spawn_code_tabs`
+    commands.entity(code_panel).insert(CodePanelTabs {
+        tab_list,
+        bodies,
+        spawned: 0,
+    });
+
+    code_panel
+}
+
+/// Spawns a scrollable code history ending with a code typer for the code tab with the given
+/// index.
+fn spawn_code_tab_body(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+    tab: usize,
+    lines_typed: f64,
+) -> Entity {
+    let hbox = commands
+        .spawn((
+            Name::new("CodePanelBody"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_grow: 1.0,
+                    ..default()
+                },
+                ..default()
+            },
+            CodeTabBody(tab),
+        ))
+        .id();
+    let scroll_view = commands
+        .spawn((
+            Name::new("CodeScrollView"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    overflow: Overflow::clip_y(),
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .set_parent(hbox)
+        .id();
+
+    let scrollbar = commands
+        .spawn((
+            Name::new("CodeScrollbar"),
+            NodeBundle {
+                style: Style {
+                    width: Px(8.0),
+                    height: Percent(100.0),
+                    justify_self: JustifySelf::End,
+                    ..default()
+                },
+                background_color: theme.info_bar_background_color.into(),
+                ..default()
+            },
+        ))
+        .set_parent(hbox)
+        .id();
+
+    let scroll_content = commands
+        .spawn((
+            Name::new("CodeScrollContent"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    padding: UiRect::all(VMin(2.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            ScrollContent {
+                position: 0.0,
+                sensitivity: 1.5,
+                scrollbar,
+            },
+        ))
+        .set_parent(scroll_view)
+        .id();
+
+    let container = commands
+        .spawn((
+            Name::new("CodeHistory"),
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .set_parent(scroll_content)
+        .id();
+
+    // Only the first tab starts with a comment for the intro to continue
+    let (text, color, syntax) = if tab == 0 {
+        (
+            "// Start typing...",
+            theme.code_panel_comment_color,
+            SyntaxHighlighter::new(theme.syntax_colors()).in_line_comment(),
+        )
+    } else {
+        (
+            "",
+            theme.code_panel_text_color,
+            SyntaxHighlighter::new(theme.syntax_colors()),
+        )
+    };
+    let typer = commands
         .spawn((
             Name::new("CodePanelText"),
             TextBundle::from_section(
-                "// Start typing...",
+                text,
                 TextStyle {
                     font: FONT_HANDLE,
-                    color: theme.code_panel_text_color,
+                    color,
                     ..default()
                 },
             )
             .with_no_wrap(),
             FontSize::new(config.code_panel_font_size),
             CodeTyper {
-                lines_count: 1,
-                lines_max: config.code_panel_lines_max,
+                tab,
+                lines_typed,
+                lines_max: config.code_panel_history_max,
+                syntax,
                 ..default()
             },
         ))
-        .set_parent(code_panel);
+        .set_parent(scroll_content)
+        .id();
 
-    code_panel
+    commands.entity(scroll_content).insert(CodeHistory {
+        typer,
+        container,
+        lines: VecDeque::new(),
+        follow: true,
+    });
+
+    hbox
+}
+
+impl EditorScreenTheme {
+    fn syntax_colors(&self) -> SyntaxColors {
+        SyntaxColors {
+            plain: self.code_panel_text_color,
+            keyword: self.code_panel_keyword_color,
+            string: self.code_panel_string_color,
+            comment: self.code_panel_comment_color,
+            number: self.code_panel_number_color,
+            macro_name: self.code_panel_macro_color,
+            lifetime: self.code_panel_lifetime_color,
+            ghost: self.code_panel_ghost_color,
+            typo: self.code_panel_typo_color,
+        }
+    }
+}
+
+/// Spawns a row for the code tabs.
+fn spawn_code_tab_bar(commands: &mut Commands, theme: &EditorScreenTheme) -> Entity {
+    commands
+        .spawn((
+            Name::new("CodeTabBar"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                background_color: theme.outline_panel_background_color.into(),
+                ..default()
+            },
+        ))
+        .id()
+}
+
+/// Spawns a tab showing the name of a code file.
+fn spawn_code_tab(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+    file_name: &str,
+) -> Entity {
+    let tab = commands
+        .spawn((
+            Name::new("CodeTab"),
+            NodeBundle {
+                style: Style {
+                    padding: UiRect::axes(VMin(2.0), VMin(0.8)),
+                    border: UiRect::top(Px(2.0)),
+                    ..default()
+                },
+                background_color: theme.code_panel_background_color.into(),
+                border_color: theme.code_panel_text_color.into(),
+                ..default()
+            },
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("CodeTabText"),
+            TextBundle::from_section(
+                file_name,
+                TextStyle {
+                    font: FONT_HANDLE,
+                    color: theme.code_panel_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.code_panel_font_size),
+        ))
+        .set_parent(tab);
+
+    tab
+}
+
+/// Spawns a row showing the source file being typed in the focused tab.
+fn spawn_code_file_header(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+) -> Entity {
+    let file_header = commands
+        .spawn((
+            Name::new("CodeFileHeader"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    padding: UiRect::axes(VMin(2.0), VMin(0.8)),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("CodeFileText"),
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: FONT_HANDLE,
+                    color: theme.code_panel_comment_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.code_panel_font_size),
+            IsCodeFileText,
+        ))
+        .set_parent(file_header);
+
+    file_header
+}
+
+/// Spawns a hidden panel that shows the active compiler error over the code.
+fn spawn_compiler_error_panel(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+) -> Entity {
+    let error_panel = commands
+        .spawn((
+            Name::new("CompilerErrorPanel"),
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    left: Val::ZERO,
+                    right: Val::ZERO,
+                    bottom: Val::ZERO,
+                    padding: UiRect::all(VMin(2.0)),
+                    border: UiRect::top(Px(2.0)),
+                    ..default()
+                },
+                background_color: theme.code_panel_background_color.into(),
+                border_color: theme.code_panel_typo_color.into(),
+                ..default()
+            },
+            IsCompilerErrorPanel,
+        ))
+        .id();
+
+    let style = |font, color| TextStyle {
+        font,
+        color,
+        ..default()
+    };
+    commands
+        .spawn((
+            Name::new("CompilerErrorText"),
+            TextBundle::from_sections([
+                // Error code
+                TextSection::from_style(style(BOLD_FONT_HANDLE, theme.code_panel_typo_color)),
+                // Message
+                TextSection::from_style(style(BOLD_FONT_HANDLE, theme.code_panel_text_color)),
+                // Location
+                TextSection::from_style(style(FONT_HANDLE, theme.code_panel_comment_color)),
+                // Help
+                TextSection::from_style(style(FONT_HANDLE, theme.code_panel_comment_color)),
+                // Typed part of the fix
+                TextSection::from_style(style(FONT_HANDLE, theme.code_panel_text_color)),
+                // Rest of the fix
+                TextSection::from_style(style(FONT_HANDLE, theme.code_panel_ghost_color)),
+                // Backspace help
+                TextSection::from_style(style(FONT_HANDLE, theme.code_panel_comment_color)),
+            ]),
+            FontSize::new(config.code_panel_font_size),
+            IsCompilerErrorText,
+        ))
+        .set_parent(error_panel);
+
+    error_panel
+}
+
+/// Spawns a button that toggles accuracy mode.
+fn spawn_accuracy_button(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+) -> Entity {
+    let accuracy_button = commands
+        .spawn((
+            Name::new("AccuracyButton"),
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::axes(VMin(2.0), VMin(0.8)),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: theme.upgrade_button_normal_color.into(),
+                ..default()
+            },
+            InteractionPalette {
+                normal: theme.upgrade_button_normal_color,
+                hovered: theme.upgrade_button_hovered_color,
+                pressed: theme.upgrade_button_pressed_color,
+                disabled: theme.upgrade_button_disabled_color,
+            },
+            On::<Pointer<Click>>::run(|mut accuracy: ResMut<AccuracyMode>| {
+                accuracy.0 = !accuracy.0;
+            }),
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("AccuracyButtonText"),
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: FONT_HANDLE,
+                    color: theme.upgrade_button_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.code_panel_font_size),
+            IsAccuracyButtonText,
+        ))
+        .set_parent(accuracy_button);
+
+    accuracy_button
+}
+
+fn spawn_code_panel_body(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Name::new("CodePanelBody"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    padding: UiRect::all(VMin(2.0)),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id()
+}
+
+/// Moves finished lines from the code typer into the history, reusing the oldest lines once the
+/// history is full.
+fn update_code_history(
+    mut commands: Commands,
+    config: Res<Config>,
+    mut despawn: ResMut<DespawnSet>,
+    mut history_query: Query<&mut CodeHistory>,
+    mut typer_query: Query<&mut CodeTyper>,
+    mut text_query: Query<&mut Text>,
+) {
+    let config = &config.editor_screen;
+    for mut history in &mut history_query {
+        let Ok(mut typer) = typer_query.get_mut(history.typer) else {
+            continue;
+        };
+        if typer.finished_lines.is_empty() {
+            continue;
+        }
+
+        // Despawn the lines that no longer fit, in case the limit was lowered
+        let excess = history.lines.len().saturating_sub(typer.lines_max);
+        for line in history.lines.drain(..excess) {
+            despawn.recursive(line);
+        }
+
+        // Lines that would be pushed out in the same frame are never shown
+        let skip = typer.finished_lines.len().saturating_sub(typer.lines_max);
+        let lines_max = typer.lines_max;
+        for sections in typer.finished_lines.drain(..).skip(skip) {
+            if history.lines.len() < lines_max {
+                let line = commands
+                    .spawn((
+                        Name::new("CodeLine"),
+                        TextBundle::from_sections(sections).with_no_wrap(),
+                        FontSize::new(config.code_panel_font_size),
+                    ))
+                    .set_parent(history.container)
+                    .id();
+                history.lines.push_back(line);
+                continue;
+            }
+
+            // Rewrite the oldest line and move it to the end
+            let Some(line) = history.lines.pop_front() else {
+                continue;
+            };
+            if let Ok(mut text) = text_query.get_mut(line) {
+                // `FontSize` only rescales on resize, so keep the current font size
+                let font_size = text.sections.first().map(|section| section.style.font_size);
+                text.sections = sections;
+                if let Some(font_size) = font_size {
+                    for section in &mut text.sections {
+                        section.style.font_size = font_size;
+                    }
+                }
+            }
+            commands.entity(history.container).push_children(&[line]);
+            history.lines.push_back(line);
+        }
+    }
+}
+
+/// The distance that the content can be scrolled, as of the last layout.
+fn scroll_overflow(node: &Node, parent: &Parent, node_query: &Query<&Node>) -> f32 {
+    let parent_height = node_query
+        .get(parent.get())
+        .map_or(0.0, |parent| parent.size().y);
+    (node.size().y - parent_height).max(0.0)
+}
+
+/// Scrolls the code history to the newest line, before the player gets a chance to scroll.
+fn follow_code_history(
+    mut history_query: Query<(&CodeHistory, &mut ScrollContent, &Node, &Parent)>,
+    node_query: Query<&Node>,
+) {
+    for (history, mut scroll, node, parent) in &mut history_query {
+        if history.follow {
+            scroll.position = -scroll_overflow(node, parent, &node_query);
+        }
+    }
+}
+
+/// Stops following the newest line while the player has scrolled up.
+fn update_code_history_follow(
+    mut history_query: Query<(&mut CodeHistory, &ScrollContent, &Node, &Parent)>,
+    node_query: Query<&Node>,
+) {
+    for (mut history, scroll, node, parent) in &mut history_query {
+        let follow = scroll.position <= 1.0 - scroll_overflow(node, parent, &node_query);
+        if history.follow != follow {
+            history.follow = follow;
+        }
+    }
+}
+
+/// Opens the code tabs of newly installed upgrades.
+fn open_code_tabs(
+    mut events: EventReader<UpgradeEvent>,
+    upgrade_list: Res<UpgradeList>,
+    mut tabs: ResMut<CodeTabs>,
+) {
+    for event in events.read() {
+        if let Some(code_file) = &upgrade_list[event.kind].code_file {
+            tabs.open(code_file);
+        }
+    }
+}
+
+/// Spawns the tabs that were opened since the code panel was spawned.
+fn spawn_code_tabs(
+    mut commands: Commands,
+    config: Res<Config>,
+    theme: Res<ActiveEditorTheme>,
+    tabs: Res<CodeTabs>,
+    mut panel_query: Query<&mut CodePanelTabs>,
+) {
+    let config = &config.editor_screen;
+    let theme = &theme.0;
+    for mut panel in &mut panel_query {
+        for (index, tab) in tabs.tabs.iter().enumerate().skip(panel.spawned) {
+            let tab_button = spawn_code_tab(&mut commands, config, theme, &tab.name);
+            commands
+                .entity(tab_button)
+                .insert((
+                    On::<Pointer<Click>>::run(move |mut tabs: ResMut<CodeTabs>| {
+                        tabs.focused = index;
+                    }),
+                    CodeTabButton(index),
+                ))
+                .set_parent(panel.tab_list);
+
+            let body = spawn_code_tab_body(&mut commands, config, theme, index, tab.lines);
+            commands.entity(body).set_parent(panel.bodies);
+        }
+        panel.spawned = tabs.tabs.len();
+    }
+}
+
+/// Saves the number of lines typed in each tab, so that the counts survive the code panel being
+/// respawned.
+fn record_code_tab_lines(mut tabs: ResMut<CodeTabs>, typer_query: Query<&CodeTyper>) {
+    for typer in &typer_query {
+        if let Some(tab) = tabs.tabs.get_mut(typer.tab) {
+            if tab.lines != typer.lines_typed {
+                tab.lines = typer.lines_typed;
+            }
+        }
+    }
+}
+
+/// Shows the body of the focused tab and highlights its button.
+fn update_code_tab_focus(
+    theme: Res<ActiveEditorTheme>,
+    tabs: Res<CodeTabs>,
+    mut button_query: Query<(&CodeTabButton, &mut BackgroundColor, &mut BorderColor)>,
+    mut body_query: Query<(&CodeTabBody, &mut Style)>,
+) {
+    let theme = &theme.0;
+    for (button, mut background_color, mut border_color) in &mut button_query {
+        let (background, border) = if button.0 == tabs.focused {
+            (
+                theme.code_panel_background_color,
+                theme.code_panel_text_color,
+            )
+        } else {
+            (theme.outline_panel_background_color, Color::NONE)
+        };
+        if background_color.0 != background {
+            background_color.0 = background;
+        }
+        if border_color.0 != border {
+            border_color.0 = border;
+        }
+    }
+
+    for (body, mut style) in &mut body_query {
+        let display = if body.0 == tabs.focused {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+/// Shows the source file being typed in the focused tab.
+fn update_code_file_text(
+    tabs: Res<CodeTabs>,
+    typer_query: Query<&CodeTyper>,
+    mut file_text_query: Query<&mut Text, With<IsCodeFileText>>,
+) {
+    let Some(typer) = typer_query.iter().find(|typer| typer.tab == tabs.focused) else {
+        return;
+    };
+    let file_name = typer.code.file_name();
+
+    for mut text in &mut file_text_query {
+        if text.sections[0].value != file_name {
+            text.sections[0].value = file_name.to_string();
+        }
+    }
+}
+
+fn update_accuracy_button_text(
+    config: Res<Config>,
+    accuracy: Res<AccuracyMode>,
+    tabs: Res<CodeTabs>,
+    typer_query: Query<&CodeTyper>,
+    mut button_text_query: Query<&mut Text, With<IsAccuracyButtonText>>,
+) {
+    let focused = typer_query.iter().find(|typer| typer.tab == tabs.focused);
+    let info = match focused {
+        Some(typer) if accuracy.0 => format!(
+            "Accuracy: On (combo x{:.2})",
+            typer.combo_multiplier(&config.code_typer),
+        ),
+        _ => "Accuracy: Off".to_string(),
+    };
+
+    for mut text in &mut button_text_query {
+        if text.sections[0].value != info {
+            text.sections[0].value = info.clone();
+        }
+    }
+}
+
+fn update_compiler_error_panel(
+    errors: Res<CompilerErrors>,
+    tabs: Res<CodeTabs>,
+    mut panel_query: Query<&mut Style, With<IsCompilerErrorPanel>>,
+    mut text_query: Query<&mut Text, With<IsCompilerErrorText>>,
+) {
+    let display = if errors.active.is_some() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in &mut panel_query {
+        if style.display != display {
+            style.display = display;
+        }
+    }
+
+    let Some(error) = &errors.active else {
+        return;
+    };
+    let location = match tabs.tabs.get(tabs.focused) {
+        Some(tab) => format!("  --> src/{}:{}:5\n", tab.name, tab.lines as usize + 1),
+        None => String::new(),
+    };
+    let typed = error
+        .fix
+        .char_indices()
+        .nth(error.typed)
+        .map_or(error.fix.len(), |(i, _)| i);
+    let values = [
+        format!("error[{}]", error.code),
+        format!(": {}\n", error.message),
+        location,
+        "help: type ".to_string(),
+        error.fix[..typed].to_string(),
+        error.fix[typed..].to_string(),
+        format!(" or press Backspace {} more times", error.backspaces),
+    ];
+
+    for mut text in &mut text_query {
+        for (section, value) in text.sections.iter_mut().zip(&values) {
+            if &section.value != value {
+                section.value = value.clone();
+            }
+        }
+    }
 }
@@ -1,10 +1,19 @@
 use bevy::prelude::*;
 use bevy::ui::Val::*;
+use bevy_mod_picking::prelude::*;
+use strum::IntoEnumIterator;
 
+use crate::simulation::GameSpeed;
+use crate::simulation::GameTime;
 use crate::simulation::Simulation;
+use crate::simulation::TypingSpeed;
 use crate::state::editor_screen::EditorScreenConfig;
 use crate::state::editor_screen::EditorScreenTheme;
+use crate::state::editor_screen::JamClock;
+use crate::ui::Disabled;
 use crate::ui::FontSize;
+use crate::ui::InteractionPalette;
+use crate::ui::FONT_HANDLE;
 use crate::ui::HEADER_FONT_HANDLE;
 use crate::util::pretty_num;
 use crate::AppSet;
@@ -14,7 +23,17 @@ pub struct InfoBarPlugin;
 impl Plugin for InfoBarPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<InfoBarText>()
-            .add_systems(Update, update_info_bar_text.in_set(AppSet::Update));
+            .register_type::<JamClockText>()
+            .register_type::<GameSpeedButton>()
+            .add_systems(
+                Update,
+                (
+                    update_info_bar_text,
+                    update_jam_clock_text,
+                    update_game_speed_buttons,
+                )
+                    .in_set(AppSet::Update),
+            );
     }
 }
 
@@ -33,6 +52,7 @@ pub fn spawn_info_bar(
                     min_height: config.info_bar_height,
                     padding: UiRect::horizontal(Px(16.0)),
                     align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
                     ..default()
                 },
                 background_color: theme.info_bar_background_color.into(),
@@ -57,26 +77,155 @@ pub fn spawn_info_bar(
         ))
         .set_parent(info_bar);
 
+    let hbox = commands
+        .spawn((
+            Name::new("HBox"),
+            NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    column_gap: Px(8.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .set_parent(info_bar)
+        .id();
+
+    commands
+        .spawn((
+            Name::new("JamClockText"),
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: theme.info_bar_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.info_bar_font_size),
+            JamClockText,
+        ))
+        .set_parent(hbox);
+
+    for speed in GameSpeed::iter() {
+        let button = spawn_game_speed_button(commands, config, theme, speed);
+        commands.entity(button).set_parent(hbox);
+    }
+
     info_bar
 }
 
+fn spawn_game_speed_button(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+    speed: GameSpeed,
+) -> Entity {
+    let button = commands
+        .spawn((
+            Name::new("GameSpeedButton"),
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::axes(Px(8.0), Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: theme.upgrade_button_normal_color.into(),
+                ..default()
+            },
+            Disabled(false),
+            InteractionPalette {
+                normal: theme.upgrade_button_normal_color,
+                hovered: theme.upgrade_button_hovered_color,
+                pressed: theme.upgrade_button_pressed_color,
+                // The current speed is shown as disabled
+                disabled: theme.upgrade_button_disabled_color,
+            },
+            On::<Pointer<Click>>::run(move |mut game_time: ResMut<GameTime>| {
+                game_time.speed = speed;
+            }),
+            GameSpeedButton(speed),
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("GameSpeedButtonText"),
+            TextBundle::from_section(
+                speed.label(),
+                TextStyle {
+                    font: FONT_HANDLE,
+                    color: theme.upgrade_button_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.upgrade_button_font_size),
+        ))
+        .set_parent(button);
+
+    button
+}
+
 #[derive(Component, Reflect)]
 struct InfoBarText;
 
 fn update_info_bar_text(
     simulation: Res<Simulation>,
+    speed: Res<TypingSpeed>,
     mut info_bar_query: Query<&mut Text, With<InfoBarText>>,
 ) {
     let lines = pretty_num(simulation.lines.floor());
     let entities = pretty_num(simulation.entities.floor());
 
     let info = format!(
-        "{lines} line{} and {entities} entit{}",
+        "{lines} line{} and {entities} entit{} | {:.0} WPM ({:.1} keys/s)",
         if lines == "1" { "" } else { "s" },
-        if entities == "1" { "y" } else { "ies" }
+        if entities == "1" { "y" } else { "ies" },
+        speed.wpm,
+        speed.keys_per_second,
     );
 
     for mut text in &mut info_bar_query {
         text.sections[0].value = info.clone();
     }
 }
+
+#[derive(Component, Reflect)]
+struct JamClockText;
+
+fn update_jam_clock_text(
+    clock: Res<JamClock>,
+    mut clock_text_query: Query<&mut Text, With<JamClockText>>,
+) {
+    let info = if clock.has_deadline() {
+        format!(
+            "Day {} of {}, {:02}:00",
+            clock.day().min(clock.days()),
+            clock.days(),
+            clock.hour(),
+        )
+    } else {
+        format!("Day {}, {:02}:00", clock.day(), clock.hour())
+    };
+
+    for mut text in &mut clock_text_query {
+        text.sections[0].value = info.clone();
+    }
+}
+
+#[derive(Component, Reflect)]
+struct GameSpeedButton(GameSpeed);
+
+fn update_game_speed_buttons(
+    game_time: Res<GameTime>,
+    mut button_query: Query<(&GameSpeedButton, &mut Disabled)>,
+) {
+    for (button, mut disabled) in &mut button_query {
+        let is_current = button.0 == game_time.speed;
+        if disabled.0 != is_current {
+            disabled.0 = is_current;
+        }
+    }
+}
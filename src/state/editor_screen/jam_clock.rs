@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+
+use crate::config::Config;
+use crate::simulation::GameMode;
+use crate::simulation::GameTime;
+use crate::state::editor_screen::EditorScreenConfig;
+use crate::state::AppState;
+use crate::AppSet;
+
+pub struct JamClockPlugin;
+
+impl Plugin for JamClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<JamClock>()
+            .init_resource::<JamClock>()
+            .add_systems(OnEnter(AppState::EditorScreen), reset_jam_clock)
+            .add_systems(
+                Update,
+                tick_jam_clock
+                    .in_set(AppSet::Tick)
+                    .run_if(in_state(AppState::EditorScreen)),
+            );
+    }
+}
+
+/// Maps elapsed time during the jam to in-game days and hours.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct JamClock {
+    /// Seconds elapsed since the jam started.
+    pub elapsed: f64,
+    /// Seconds until the jam deadline, measured from the start of the jam.
+    pub deadline: f64,
+    /// Seconds per in-game day.
+    pub day_duration: f64,
+}
+
+impl FromWorld for JamClock {
+    fn from_world(world: &mut World) -> Self {
+        let mode = world.get_resource::<GameMode>().copied().unwrap_or_default();
+        Self::from_config(&world.resource::<Config>().editor_screen, mode)
+    }
+}
+
+impl JamClock {
+    /// Starts a jam with the deadline from config, or no deadline in sandbox mode.
+    fn from_config(config: &EditorScreenConfig, mode: GameMode) -> Self {
+        let days = match mode {
+            GameMode::Jam | GameMode::DailyChallenge => config.jam_clock_days,
+            GameMode::Sandbox => f64::INFINITY,
+        };
+        let day_duration = config.jam_clock_day_duration;
+        Self {
+            elapsed: 0.0,
+            deadline: days * day_duration,
+            day_duration,
+        }
+    }
+
+    /// The current in-game day, starting from 1.
+    pub fn day(&self) -> usize {
+        (self.elapsed.min(self.deadline) / self.day_duration).floor() as usize + 1
+    }
+
+    /// The current in-game hour of the day (between 0 and 23).
+    pub fn hour(&self) -> usize {
+        let day_progress = (self.elapsed.min(self.deadline) / self.day_duration).fract();
+        (day_progress * 24.0).floor() as usize
+    }
+
+    /// The total number of in-game days until the deadline, rounded up.
+    pub fn days(&self) -> usize {
+        (self.deadline / self.day_duration).ceil() as usize
+    }
+
+    /// Returns false if the jam has no deadline (in sandbox mode).
+    pub fn has_deadline(&self) -> bool {
+        self.deadline.is_finite()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.elapsed >= self.deadline
+    }
+
+    /// Moves the deadline by the given number of in-game hours.
+    pub fn extend_by_hours(&mut self, hours: f64) {
+        self.deadline += hours / 24.0 * self.day_duration;
+    }
+}
+
+fn reset_jam_clock(mut commands: Commands, config: Res<Config>, mode: Res<GameMode>) {
+    commands.insert_resource(JamClock::from_config(&config.editor_screen, *mode));
+}
+
+fn tick_jam_clock(
+    time: Res<GameTime>,
+    mut clock: ResMut<JamClock>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if clock.is_over() {
+        return;
+    }
+
+    clock.elapsed += time.delta_seconds_f64();
+    if clock.is_over() {
+        // Time's up: submit whatever we have
+        next_state.set(AppState::ResultsScreen);
+    }
+}
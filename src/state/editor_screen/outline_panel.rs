@@ -9,6 +9,7 @@ use crate::state::editor_screen::ActiveEditorTheme;
 use crate::state::editor_screen::EditorScreenConfig;
 use crate::state::editor_screen::EditorScreenTheme;
 use crate::state::AppState;
+use crate::ui::CodeTabs;
 use crate::ui::FontSize;
 use crate::ui::InteractionPalette;
 use crate::ui::ScrollContent;
@@ -19,6 +20,7 @@ use crate::ui::HEADER_FONT_HANDLE;
 use crate::upgrade::UpgradeEvent;
 use crate::upgrade::UpgradeKind;
 use crate::upgrade::UpgradeList;
+use crate::util::pretty_num;
 use crate::AppSet;
 
 pub struct OutlinePanelPlugin;
@@ -27,13 +29,17 @@ impl Plugin for OutlinePanelPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<IsOutlineContainer>()
             .register_type::<IsOutlineHeader>()
+            .register_type::<IsOutlineFiles>()
             .register_type::<UpgradeOutline>()
             .init_resource::<UpgradeOutline>()
             .add_systems(
                 Update,
                 (
-                    update_outline_container.run_if(in_state(AppState::EditorScreen)),
+                    (restore_outline_entries, update_outline_container)
+                        .chain()
+                        .run_if(in_state(AppState::EditorScreen)),
                     update_outline_header,
+                    update_outline_files,
                     update_outline_entry_text.run_if(on_event::<UpgradeEvent>()),
                 )
                     .in_set(AppSet::Update),
@@ -91,6 +97,29 @@ pub fn spawn_outline_panel(
         ))
         .set_parent(outline_panel);
 
+    commands
+        .spawn((
+            Name::new("OutlineFiles"),
+            TextBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font: FONT_HANDLE,
+                        color: theme.outline_panel_text_color,
+                        ..default()
+                    },
+                ),
+                style: Style {
+                    margin: UiRect::bottom(Px(10.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            FontSize::new(config.outline_panel_font_size),
+            IsOutlineFiles,
+        ))
+        .set_parent(outline_panel);
+
     let hbox = commands
         .spawn((
             Name::new("HBox"),
@@ -263,6 +292,46 @@ fn update_outline_container(
     }
 }
 
+/// Fills a newly spawned outline container (e.g. after the editor theme changes) with the
+/// upgrades that were already installed.
+fn restore_outline_entries(
+    mut commands: Commands,
+    config: Res<Config>,
+    theme: Res<ActiveEditorTheme>,
+    upgrade_list: Res<UpgradeList>,
+    outline: Res<UpgradeOutline>,
+    container_query: Query<Entity, Added<IsOutlineContainer>>,
+) {
+    let config = &config.editor_screen;
+    let theme = &theme.0;
+    let mut entries = outline
+        .0
+        .iter()
+        .filter(|(&kind, _)| !upgrade_list[kind].no_outline)
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    for container in &container_query {
+        for &(&kind, &count) in &entries {
+            let upgrade = &upgrade_list[kind];
+            let mut name = upgrade.name.clone();
+            if !upgrade.no_count && count >= 2 {
+                name.push_str(&format!(" ({count})"));
+            }
+
+            let outline_entry = spawn_outline_entry(
+                &mut commands,
+                config,
+                theme,
+                kind,
+                name,
+                upgrade.description(),
+            );
+            commands.entity(outline_entry).set_parent(container);
+        }
+    }
+}
+
 #[derive(Component, Reflect)]
 struct IsOutlineHeader;
 
@@ -277,6 +346,32 @@ fn update_outline_header(
     }
 }
 
+#[derive(Component, Reflect)]
+struct IsOutlineFiles;
+
+/// Lists the open code files with the number of lines typed in each.
+fn update_outline_files(
+    tabs: Res<CodeTabs>,
+    mut files_query: Query<&mut Text, With<IsOutlineFiles>>,
+) {
+    let info = tabs
+        .tabs
+        .iter()
+        .map(|tab| {
+            let lines = pretty_num(tab.lines.floor());
+            let s = if lines == "1" { "" } else { "s" };
+            format!("{} ({lines} line{s})", tab.name)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    for mut text in &mut files_query {
+        if text.sections[0].value != info {
+            text.sections[0].value = info.clone();
+        }
+    }
+}
+
 #[derive(Component, Reflect)]
 struct OutlineEntry(UpgradeKind);
 
@@ -0,0 +1,468 @@
+use bevy::prelude::*;
+use bevy::ui::Val::*;
+use bevy_mod_picking::prelude::*;
+use rand::thread_rng;
+use strum::EnumIter;
+use strum::IntoEnumIterator;
+
+use crate::config::Config;
+use crate::physics::PhysicsSettings;
+use crate::physics::UNIT_SPEED;
+use crate::simulation::AtlasList;
+use crate::simulation::GameMode;
+use crate::simulation::Simulation;
+use crate::simulation::SpritePackEvent;
+use crate::state::editor_screen::spawn_editor_screen;
+use crate::state::editor_screen::ActiveEditorTheme;
+use crate::state::editor_screen::EditorScreenConfig;
+use crate::state::editor_screen::EditorScreenTheme;
+use crate::state::AppState;
+use crate::ui::FontSize;
+use crate::ui::InteractionPalette;
+use crate::ui::FONT_HANDLE;
+use crate::ui::HEADER_FONT_HANDLE;
+use crate::util::pretty_num;
+use crate::AppRoot;
+use crate::AppSet;
+
+pub struct SandboxPanelPlugin;
+
+impl Plugin for SandboxPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<IsSandboxPanel>()
+            .register_type::<IsSpritePackRow>()
+            .register_type::<SandboxSetting>()
+            .add_systems(
+                Update,
+                (spawn_sprite_pack_buttons, update_sandbox_setting_text)
+                    .in_set(AppSet::Update)
+                    .run_if(in_state(AppState::EditorScreen)),
+            );
+    }
+}
+
+/// A live setting that can be edited from the sandbox panel.
+#[derive(Component, Reflect, Copy, Clone, EnumIter)]
+enum SandboxSetting {
+    LineMultiplier,
+    EntitySpawnPerLine,
+    EntitySpawnMultiplier,
+    TechDebtMultiplier,
+    EntitySizeMin,
+    EntitySizeMax,
+    EntitySpeed,
+    MouseForce,
+}
+
+impl SandboxSetting {
+    fn label(self) -> &'static str {
+        match self {
+            Self::LineMultiplier => "Line multiplier",
+            Self::EntitySpawnPerLine => "Entities per line",
+            Self::EntitySpawnMultiplier => "Entity multiplier",
+            Self::TechDebtMultiplier => "Tech debt multiplier",
+            Self::EntitySizeMin => "Min entity size",
+            Self::EntitySizeMax => "Max entity size",
+            Self::EntitySpeed => "Entity speed",
+            Self::MouseForce => "Mouse force",
+        }
+    }
+
+    fn value(self, simulation: &Simulation, physics: &PhysicsSettings) -> f64 {
+        match self {
+            Self::LineMultiplier => simulation.line_multiplier,
+            Self::EntitySpawnPerLine => simulation.entity_spawn_per_line,
+            Self::EntitySpawnMultiplier => simulation.entity_spawn_multiplier,
+            Self::TechDebtMultiplier => simulation.tech_debt_multiplier,
+            Self::EntitySizeMin => simulation.entity_size_min as f64,
+            Self::EntitySizeMax => simulation.entity_size_max as f64,
+            Self::EntitySpeed => physics.speed_multiplier as f64,
+            Self::MouseForce => physics.mouse_force_strength as f64,
+        }
+    }
+
+    /// Increases or decreases the setting by one step.
+    fn step(self, simulation: &mut Simulation, physics: &mut PhysicsSettings, increase: bool) {
+        let sign = if increase { 1.0 } else { -1.0 };
+        let factor = if increase { 2.0 } else { 0.5 };
+        match self {
+            Self::LineMultiplier => simulation.line_multiplier *= factor,
+            Self::EntitySpawnPerLine => {
+                simulation.entity_spawn_per_line =
+                    (simulation.entity_spawn_per_line + sign).max(0.0);
+            },
+            Self::EntitySpawnMultiplier => simulation.entity_spawn_multiplier *= factor,
+            Self::TechDebtMultiplier => {
+                simulation.tech_debt_multiplier =
+                    (simulation.tech_debt_multiplier + 0.25 * sign).max(0.0);
+            },
+            Self::EntitySizeMin => {
+                simulation.entity_size_min =
+                    (simulation.entity_size_min + 2.0 * sign as f32).clamp(1.0, 128.0);
+                simulation.entity_size_max =
+                    simulation.entity_size_max.max(simulation.entity_size_min);
+            },
+            Self::EntitySizeMax => {
+                simulation.entity_size_max =
+                    (simulation.entity_size_max + 2.0 * sign as f32).clamp(1.0, 128.0);
+                simulation.entity_size_min =
+                    simulation.entity_size_min.min(simulation.entity_size_max);
+            },
+            Self::EntitySpeed => {
+                physics.speed_multiplier =
+                    (physics.speed_multiplier + UNIT_SPEED * sign as f32).max(0.0);
+            },
+            Self::MouseForce => {
+                physics.mouse_force_strength =
+                    (physics.mouse_force_strength + 1000.0 * sign as f32).max(0.0);
+            },
+        }
+    }
+}
+
+/// The editor themes that can be picked from the sandbox panel.
+#[derive(Copy, Clone, EnumIter)]
+enum SandboxTheme {
+    Light,
+    Dracula,
+    Bamboo,
+    Synthwave,
+}
+
+impl SandboxTheme {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Light => "Light",
+            Self::Dracula => "Dracula",
+            Self::Bamboo => "Bamboo",
+            Self::Synthwave => "Synthwave",
+        }
+    }
+
+    fn get(self, config: &EditorScreenConfig) -> &EditorScreenTheme {
+        match self {
+            Self::Light => &config.light_theme,
+            Self::Dracula => &config.dracula_theme,
+            Self::Bamboo => &config.bamboo_theme,
+            Self::Synthwave => &config.synthwave_theme,
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+pub struct IsSandboxPanel;
+
+pub fn spawn_sandbox_panel(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+) -> Entity {
+    let sandbox_panel = commands
+        .spawn((
+            Name::new("SandboxPanel"),
+            NodeBundle {
+                style: Style {
+                    // Hidden until toggled from the upgrade panel
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    left: config.outline_panel_width,
+                    top: config.info_bar_height,
+                    width: config.sandbox_panel_width,
+                    padding: UiRect::all(Px(12.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(6.0),
+                    ..default()
+                },
+                background_color: theme.upgrade_panel_background_color.into(),
+                z_index: ZIndex::Global(1),
+                ..default()
+            },
+            IsSandboxPanel,
+        ))
+        .id();
+
+    let header = spawn_sandbox_header(commands, config, theme, "Settings");
+    commands.entity(header).set_parent(sandbox_panel);
+
+    for setting in SandboxSetting::iter() {
+        let row = spawn_setting_row(commands, config, theme, setting);
+        commands.entity(row).set_parent(sandbox_panel);
+    }
+
+    let header = spawn_sandbox_header(commands, config, theme, "Sprite Pack");
+    commands.entity(header).set_parent(sandbox_panel);
+
+    // The buttons are spawned by `spawn_sprite_pack_buttons`
+    let row = spawn_button_row(commands);
+    commands
+        .entity(row)
+        .insert(IsSpritePackRow)
+        .set_parent(sandbox_panel);
+
+    let header = spawn_sandbox_header(commands, config, theme, "Editor Theme");
+    commands.entity(header).set_parent(sandbox_panel);
+
+    let row = spawn_button_row(commands);
+    commands.entity(row).set_parent(sandbox_panel);
+    for sandbox_theme in SandboxTheme::iter() {
+        let button = spawn_sandbox_button(
+            commands,
+            config,
+            theme,
+            sandbox_theme.label(),
+            On::<Pointer<Click>>::run(
+                move |mut commands: Commands, root: Res<AppRoot>, config: Res<Config>| {
+                    commands.entity(root.ui).despawn_descendants();
+                    let config = &config.editor_screen;
+                    let editor_screen = spawn_editor_screen(
+                        &mut commands,
+                        config,
+                        sandbox_theme.get(config),
+                        false,
+                        GameMode::Sandbox,
+                    );
+                    commands.entity(editor_screen).set_parent(root.ui);
+                },
+            ),
+        );
+        commands.entity(button).set_parent(row);
+    }
+
+    sandbox_panel
+}
+
+fn spawn_sandbox_header(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+    text: &str,
+) -> Entity {
+    commands
+        .spawn((
+            Name::new("SandboxHeader"),
+            TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: theme.upgrade_panel_text_color,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                margin: UiRect::top(Px(6.0)),
+                ..default()
+            }),
+            FontSize::new(config.upgrade_panel_header_font_size),
+        ))
+        .id()
+}
+
+#[derive(Component, Reflect)]
+struct IsSpritePackRow;
+
+/// Fills a newly spawned sprite pack row with a button for each discovered sprite pack.
+fn spawn_sprite_pack_buttons(
+    mut commands: Commands,
+    config: Res<Config>,
+    theme: Res<ActiveEditorTheme>,
+    atlas_list: Res<AtlasList>,
+    row_query: Query<Entity, Added<IsSpritePackRow>>,
+) {
+    let config = &config.editor_screen;
+    let theme = &theme.0;
+    for row in &row_query {
+        for sprite_pack in atlas_list.sprite_packs() {
+            let button = spawn_sandbox_button(
+                &mut commands,
+                config,
+                theme,
+                atlas_list.label(sprite_pack),
+                On::<Pointer<Click>>::run(
+                    move |mut events: EventWriter<SpritePackEvent>,
+                          mut simulation: ResMut<Simulation>,
+                          atlas_list: Res<AtlasList>| {
+                        simulation.replace_sprite_pack(&atlas_list, sprite_pack, thread_rng());
+                        events.send(SpritePackEvent);
+                    },
+                ),
+            );
+            commands.entity(button).set_parent(row);
+        }
+    }
+}
+
+fn spawn_button_row(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Name::new("SandboxButtonRow"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: Px(6.0),
+                    row_gap: Px(6.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id()
+}
+
+fn spawn_setting_row(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+    setting: SandboxSetting,
+) -> Entity {
+    let row = commands
+        .spawn((
+            Name::new("SandboxSettingRow"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    align_items: AlignItems::Center,
+                    column_gap: Px(6.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("SandboxSettingLabel"),
+            TextBundle::from_section(
+                setting.label(),
+                TextStyle {
+                    font: FONT_HANDLE,
+                    color: theme.upgrade_panel_text_color,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                flex_grow: 1.0,
+                ..default()
+            }),
+            FontSize::new(config.upgrade_button_font_size),
+        ))
+        .set_parent(row);
+
+    for (text, increase) in [("-", false), ("+", true)] {
+        let button = spawn_sandbox_button(
+            commands,
+            config,
+            theme,
+            text,
+            On::<Pointer<Click>>::run(
+                move |mut simulation: ResMut<Simulation>, mut physics: ResMut<PhysicsSettings>| {
+                    setting.step(&mut simulation, &mut physics, increase);
+                },
+            ),
+        );
+        commands.entity(button).set_parent(row);
+
+        if !increase {
+            commands
+                .spawn((
+                    Name::new("SandboxSettingValue"),
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: FONT_HANDLE,
+                            color: theme.upgrade_panel_text_color,
+                            ..default()
+                        },
+                    )
+                    .with_text_alignment(TextAlignment::Center)
+                    .with_style(Style {
+                        min_width: Px(80.0),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    }),
+                    FontSize::new(config.upgrade_button_font_size),
+                    setting,
+                ))
+                .set_parent(row);
+        }
+    }
+
+    row
+}
+
+fn spawn_sandbox_button(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    theme: &EditorScreenTheme,
+    text: &str,
+    on_click: On<Pointer<Click>>,
+) -> Entity {
+    let button = commands
+        .spawn((
+            Name::new("SandboxButton"),
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::axes(Px(8.0), Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: theme.upgrade_button_normal_color.into(),
+                ..default()
+            },
+            InteractionPalette {
+                normal: theme.upgrade_button_normal_color,
+                hovered: theme.upgrade_button_hovered_color,
+                pressed: theme.upgrade_button_pressed_color,
+                disabled: theme.upgrade_button_disabled_color,
+            },
+            on_click,
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("SandboxButtonText"),
+            TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: FONT_HANDLE,
+                    color: theme.upgrade_button_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.upgrade_button_font_size),
+        ))
+        .set_parent(button);
+
+    button
+}
+
+pub fn toggle_sandbox_panel(mut panel_query: Query<&mut Style, With<IsSandboxPanel>>) {
+    for mut style in &mut panel_query {
+        style.display = match style.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+fn update_sandbox_setting_text(
+    simulation: Res<Simulation>,
+    physics: Res<PhysicsSettings>,
+    mut text_query: Query<(&mut Text, &SandboxSetting)>,
+) {
+    for (mut text, &setting) in &mut text_query {
+        let value = setting.value(&simulation, &physics);
+        let value = if value.abs() < 1000.0 {
+            format!("{value:.2}")
+        } else {
+            pretty_num(value)
+        };
+        // Avoid re-laying out the text every frame
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
 
+use crate::simulation::GameTime;
+use crate::simulation::IncomeSource;
+use crate::simulation::Simulation;
 use crate::simulation::SpawnEvent;
 use crate::AppRoot;
 use crate::AppSet;
@@ -61,9 +64,14 @@ fn click_spawn(
     listener: Listener<Pointer<Down>>,
     mut events: EventWriter<SpawnEvent>,
     root: Res<AppRoot>,
+    game_time: Res<GameTime>,
+    simulation: Res<Simulation>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
     mut scene_view_query: Query<&mut SceneView>,
 ) {
+    if game_time.is_paused() || !simulation.allows_clicking() {
+        return;
+    }
     let Ok((camera, camera_gt)) = camera_query.get(root.camera) else {
         return;
     };
@@ -80,6 +88,7 @@ fn click_spawn(
         position,
         count: scene_view.spawns_per_click,
         custom_cap: Some(80),
+        source: IncomeSource::Click,
     });
     scene_view.spawns_per_click *= scene_view.spawns_per_click_multiplier_per_click;
 }
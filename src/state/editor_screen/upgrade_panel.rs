@@ -4,7 +4,10 @@ use bevy::ui::Val::*;
 use bevy_mod_picking::prelude::*;
 
 use crate::config::Config;
+use crate::simulation::GameMode;
+use crate::simulation::RunRng;
 use crate::simulation::Simulation;
+use crate::state::editor_screen::sandbox_panel::toggle_sandbox_panel;
 use crate::state::editor_screen::ActiveEditorTheme;
 use crate::state::editor_screen::EditorScreenConfig;
 use crate::state::editor_screen::EditorScreenTheme;
@@ -51,6 +54,7 @@ pub fn spawn_upgrade_panel(
     commands: &mut Commands,
     config: &EditorScreenConfig,
     theme: &EditorScreenTheme,
+    mode: GameMode,
 ) -> Entity {
     let upgrade_panel = commands
         .spawn((
@@ -116,6 +120,7 @@ pub fn spawn_upgrade_panel(
                     height: Px(130.0),
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
+                    column_gap: Px(10.0),
                     ..default()
                 },
                 ..default()
@@ -124,8 +129,39 @@ pub fn spawn_upgrade_panel(
         .set_parent(upgrade_panel)
         .id();
 
-    let submit_button = spawn_submit_button(commands, config);
-    commands.entity(submit_button).set_parent(submit_container);
+    let buttons = match mode {
+        GameMode::Jam | GameMode::DailyChallenge => vec![spawn_submit_button(
+            commands,
+            config,
+            "Submit",
+            Percent(80.0),
+            On::<Pointer<Click>>::run(|mut next_state: ResMut<NextState<_>>| {
+                next_state.set(AppState::ResultsScreen);
+            }),
+        )],
+        // There's nothing to submit in sandbox mode
+        GameMode::Sandbox => vec![
+            spawn_submit_button(
+                commands,
+                config,
+                "Tweak",
+                Percent(45.0),
+                On::<Pointer<Click>>::run(toggle_sandbox_panel),
+            ),
+            spawn_submit_button(
+                commands,
+                config,
+                "Quit",
+                Percent(45.0),
+                On::<Pointer<Click>>::run(|mut next_state: ResMut<NextState<_>>| {
+                    next_state.set(AppState::TitleScreen);
+                }),
+            ),
+        ],
+    };
+    for button in buttons {
+        commands.entity(button).set_parent(submit_container);
+    }
 
     upgrade_panel
 }
@@ -170,7 +206,7 @@ fn spawn_upgrade_button(
                 // I have created technical debt to display the technical debt
                 text: format!(
                     "{}{}",
-                    &upgrade.description(),
+                    upgrade.description(),
                     if upgrade.tech_debt == 0.0 {
                         "".to_string()
                     } else {
@@ -269,13 +305,19 @@ fn spawn_separator(
         .id()
 }
 
-fn spawn_submit_button(commands: &mut Commands, config: &EditorScreenConfig) -> Entity {
+fn spawn_submit_button(
+    commands: &mut Commands,
+    config: &EditorScreenConfig,
+    text: &str,
+    width: Val,
+    on_click: On<Pointer<Click>>,
+) -> Entity {
     let submit_button = commands
         .spawn((
             Name::new("SubmitButton"),
             ButtonBundle {
                 style: Style {
-                    width: Percent(80.0),
+                    width,
                     height: config.submit_button_height,
                     padding: UiRect::all(Px(10.0)),
                     justify_content: JustifyContent::Center,
@@ -291,9 +333,7 @@ fn spawn_submit_button(commands: &mut Commands, config: &EditorScreenConfig) ->
                 pressed: config.submit_button_pressed_color,
                 disabled: Color::NONE,
             },
-            On::<Pointer<Click>>::run(|mut next_state: ResMut<NextState<_>>| {
-                next_state.set(AppState::ResultsScreen);
-            }),
+            on_click,
         ))
         .id();
 
@@ -301,7 +341,7 @@ fn spawn_submit_button(commands: &mut Commands, config: &EditorScreenConfig) ->
         .spawn((
             Name::new("SubmitButtonText"),
             TextBundle::from_section(
-                "Submit",
+                text,
                 TextStyle {
                     font: HEADER_FONT_HANDLE,
                     color: config.submit_button_text_color,
@@ -340,6 +380,7 @@ fn offer_next_upgrades(
     mut sequence: ResMut<UpgradeSequence>,
     simulation: Res<Simulation>,
     outline: Res<UpgradeOutline>,
+    mut rng: ResMut<RunRng>,
     container_query: Query<(Entity, Option<&Children>), With<IsUpgradeContainer>>,
 ) {
     let config = &config.editor_screen;
@@ -350,7 +391,8 @@ fn offer_next_upgrades(
             despawn.recursive(button);
         }
 
-        let (next_upgrades, desc) = sequence.next(&upgrade_list, &simulation, &outline);
+        let (next_upgrades, desc) =
+            sequence.next(&upgrade_list, &simulation, &outline, &mut rng.upgrades);
 
         for kind in next_upgrades {
             if kind == UpgradeKind::RefreshUpgradeList {
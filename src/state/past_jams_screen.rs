@@ -0,0 +1,519 @@
+use std::cmp::Ordering;
+
+use bevy::prelude::*;
+use bevy::ui::Val::*;
+use bevy_mod_picking::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+use strum::EnumIter;
+use strum::IntoEnumIterator;
+
+use crate::config::Config;
+use crate::history::JamHistory;
+use crate::history::JamRecord;
+use crate::state::AppState;
+use crate::state::AppState::*;
+use crate::ui::FontSize;
+use crate::ui::InteractionPalette;
+use crate::ui::ScrollContent;
+use crate::ui::Tooltip;
+use crate::ui::TooltipSide;
+use crate::ui::BOLD_FONT_HANDLE;
+use crate::ui::FONT_HANDLE;
+use crate::ui::HEADER_FONT_HANDLE;
+use crate::util::pretty_num;
+use crate::AppRoot;
+use crate::AppSet;
+
+pub struct PastJamsScreenStatePlugin;
+
+impl Plugin for PastJamsScreenStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PastJamsScreenConfig>()
+            .register_type::<PastJamsSort>()
+            .register_type::<SortButton>()
+            .register_type::<IsPastJamsList>()
+            .init_resource::<PastJamsSort>()
+            .add_systems(OnEnter(PastJamsScreen), enter_past_jams_screen)
+            .add_systems(OnExit(PastJamsScreen), exit_past_jams_screen)
+            .add_systems(
+                Update,
+                (update_sort_buttons, update_past_jams_list)
+                    .in_set(AppSet::Update)
+                    .run_if(in_state(PastJamsScreen))
+                    .run_if(resource_changed::<PastJamsSort>()),
+            );
+    }
+}
+
+#[derive(Default, Reflect, Serialize, Deserialize)]
+pub struct PastJamsScreenConfig {
+    background_color: Color,
+    border_color: Color,
+    border_width: Val,
+    text_color: Color,
+    highlight_text_color: Color,
+    font_size: Val,
+
+    title_text_color: Color,
+    title_font_size: Val,
+
+    table_header_background_color: Color,
+    table_header_text_color: Color,
+    table_font_size: Val,
+
+    return_button_normal_color: Color,
+    return_button_hovered_color: Color,
+    return_button_pressed_color: Color,
+    return_button_text_color: Color,
+}
+
+const TITLE_TEXT: &str = "Past Jams";
+const SCORE_CATEGORIES: [&str; 4] = ["Fun", "Presentation", "Theme Interpretation", "Overall"];
+
+/// The column that past jams are sorted by.
+#[derive(Resource, Reflect, Default, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[reflect(Resource)]
+enum PastJamsSort {
+    #[default]
+    Date,
+    Duration,
+    Fun,
+    Presentation,
+    Theme,
+    Overall,
+    Rank,
+    Entities,
+}
+
+impl PastJamsSort {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Date => "Date",
+            Self::Duration => "Time",
+            Self::Fun => "Fun",
+            Self::Presentation => "Pres.",
+            Self::Theme => "Theme",
+            Self::Overall => "Overall",
+            Self::Rank => "Rank",
+            Self::Entities => "Entities",
+        }
+    }
+
+    /// The index into `JamRecord::scores` for this column, if it shows a score.
+    fn score_idx(self) -> Option<usize> {
+        match self {
+            Self::Fun => Some(0),
+            Self::Presentation => Some(1),
+            Self::Theme => Some(2),
+            Self::Overall => Some(3),
+            _ => None,
+        }
+    }
+
+    fn cell_text(self, record: &JamRecord) -> String {
+        if let Some(idx) = self.score_idx() {
+            return format!("{:.3}", record.scores[idx]);
+        }
+        match self {
+            Self::Date => record.date(),
+            Self::Duration => {
+                let seconds = record.duration.max(0.0) as u64;
+                format!("{}:{:02}", seconds / 60, seconds % 60)
+            },
+            Self::Rank => format!("#{} of {}", record.rank, record.entries),
+            Self::Entities => pretty_num(record.entities.floor()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Orders records from best to worst (or newest to oldest).
+    fn compare(self, a: &JamRecord, b: &JamRecord) -> Ordering {
+        if let Some(idx) = self.score_idx() {
+            return b.scores[idx].total_cmp(&a.scores[idx]);
+        }
+        match self {
+            Self::Date => b.timestamp.cmp(&a.timestamp),
+            Self::Duration => b.duration.total_cmp(&a.duration),
+            Self::Rank => a.rank.cmp(&b.rank),
+            Self::Entities => b.entities.total_cmp(&a.entities),
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn enter_past_jams_screen(
+    mut commands: Commands,
+    root: Res<AppRoot>,
+    config: Res<Config>,
+    history: Res<JamHistory>,
+) {
+    let config = &config.past_jams_screen;
+    commands.insert_resource(ClearColor(config.background_color));
+    // Trigger a rebuild of the list
+    commands.insert_resource(PastJamsSort::default());
+
+    let screen = commands
+        .spawn((
+            Name::new("PastJamsScreen"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    align_items: AlignItems::Center,
+                    padding: UiRect::new(VMin(8.3), VMin(8.3), Vh(4.0), Vh(4.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: config.background_color.into(),
+                ..default()
+            },
+        ))
+        .set_parent(root.ui)
+        .id();
+
+    commands
+        .spawn((
+            Name::new("TitleText"),
+            TextBundle::from_section(
+                TITLE_TEXT,
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: config.title_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.title_font_size),
+        ))
+        .set_parent(screen);
+
+    let bests_text = match (history.best_scores(), history.best_rank()) {
+        (Some(scores), Some(rank)) => format!(
+            "Personal bests: {}, Rank #{rank}",
+            SCORE_CATEGORIES
+                .iter()
+                .zip(scores)
+                .map(|(category, score)| format!("{category} {score:.3}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        _ => "No past jams yet. Submit an entry to see it here!".to_string(),
+    };
+    commands
+        .spawn((
+            Name::new("PersonalBestsText"),
+            TextBundle::from_section(
+                bests_text,
+                TextStyle {
+                    font: FONT_HANDLE,
+                    color: config.text_color,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                margin: UiRect::vertical(Vh(3.0)),
+                ..default()
+            }),
+            FontSize::new(config.font_size),
+        ))
+        .set_parent(screen);
+
+    let header = spawn_row(&mut commands, config);
+    commands
+        .entity(header)
+        .insert(BackgroundColor(config.table_header_background_color))
+        .set_parent(screen);
+    for key in PastJamsSort::iter() {
+        let sort_button = commands
+            .spawn((
+                Name::new("SortButton"),
+                ButtonBundle {
+                    background_color: Color::NONE.into(),
+                    ..default()
+                },
+                On::<Pointer<Click>>::run(move |mut sort: ResMut<PastJamsSort>| {
+                    *sort = key;
+                }),
+                SortButton(key),
+            ))
+            .set_parent(header)
+            .id();
+
+        commands
+            .spawn((
+                Name::new("SortButtonText"),
+                TextBundle::from_section(
+                    key.label(),
+                    TextStyle {
+                        font: BOLD_FONT_HANDLE,
+                        color: config.table_header_text_color,
+                        ..default()
+                    },
+                ),
+                FontSize::new(config.table_font_size),
+            ))
+            .set_parent(sort_button);
+    }
+
+    let list_panel = commands
+        .spawn((
+            Name::new("PastJamsPanel"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_grow: 1.0,
+                    border: UiRect::all(config.border_width),
+                    ..default()
+                },
+                border_color: config.border_color.into(),
+                ..default()
+            },
+        ))
+        .set_parent(screen)
+        .id();
+
+    let scroll_view = commands
+        .spawn((
+            Name::new("PastJamsScrollView"),
+            NodeBundle {
+                style: Style {
+                    // Restrict height to the available space in the parent node
+                    position_type: PositionType::Absolute,
+                    overflow: Overflow::clip_y(),
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    padding: UiRect::right(Px(12.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .set_parent(list_panel)
+        .id();
+
+    let scrollbar = commands
+        .spawn((
+            Name::new("PastJamsScrollbar"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::ZERO,
+                    width: Px(8.0),
+                    height: Percent(100.0),
+                    ..default()
+                },
+                background_color: config.table_header_background_color.into(),
+                ..default()
+            },
+        ))
+        .set_parent(list_panel)
+        .id();
+
+    commands
+        .spawn((
+            Name::new("PastJamsList"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            ScrollContent {
+                position: 0.0,
+                sensitivity: 1.5,
+                scrollbar,
+            },
+            IsPastJamsList,
+        ))
+        .set_parent(scroll_view);
+
+    let return_button = spawn_return_button(&mut commands, config);
+    commands.entity(return_button).set_parent(screen);
+}
+
+fn exit_past_jams_screen(mut commands: Commands, root: Res<AppRoot>) {
+    commands.entity(root.ui).despawn_descendants();
+}
+
+#[derive(Component, Reflect)]
+struct SortButton(PastJamsSort);
+
+#[derive(Component, Reflect)]
+struct IsPastJamsList;
+
+/// Spawns an empty grid row with one column per `PastJamsSort`.
+fn spawn_row(commands: &mut Commands, config: &PastJamsScreenConfig) -> Entity {
+    commands
+        .spawn((
+            Name::new("PastJamsRow"),
+            NodeBundle {
+                style: Style {
+                    display: Display::Grid,
+                    width: Percent(100.0),
+                    padding: UiRect::axes(VMin(2.0), VMin(0.8)),
+                    column_gap: VMin(2.0),
+                    grid_template_columns: RepeatedGridTrack::fr(
+                        PastJamsSort::iter().count() as u16,
+                        1.0,
+                    ),
+                    border: UiRect::bottom(config.border_width),
+                    ..default()
+                },
+                border_color: config.border_color.into(),
+                ..default()
+            },
+        ))
+        .id()
+}
+
+fn update_sort_buttons(
+    config: Res<Config>,
+    sort: Res<PastJamsSort>,
+    button_query: Query<(&SortButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let config = &config.past_jams_screen;
+    for (button, children) in &button_query {
+        let color = if button.0 == *sort {
+            config.highlight_text_color
+        } else {
+            config.table_header_text_color
+        };
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[0].style.color = color;
+            }
+        }
+    }
+}
+
+fn update_past_jams_list(
+    mut commands: Commands,
+    config: Res<Config>,
+    history: Res<JamHistory>,
+    sort: Res<PastJamsSort>,
+    list_query: Query<Entity, With<IsPastJamsList>>,
+) {
+    let config = &config.past_jams_screen;
+    let Ok(list) = list_query.get_single() else {
+        return;
+    };
+    commands.entity(list).despawn_descendants();
+
+    let best_scores = history.best_scores().unwrap_or_default();
+    let best_rank = history.best_rank().unwrap_or_default();
+    let mut records = history.records.iter().collect::<Vec<_>>();
+    records.sort_by(|a, b| sort.compare(a, b));
+
+    for record in records {
+        let row = spawn_row(&mut commands, config);
+        let upgrades = if record.upgrades.is_empty() {
+            "No upgrades.".to_string()
+        } else {
+            record
+                .upgrades
+                .iter()
+                .map(|(name, count)| format!("{name} x{count}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        commands
+            .entity(row)
+            .insert((
+                Interaction::default(),
+                Tooltip {
+                    text: format!(
+                        "{}Theme: {}\n{} lines, {} tech debt\n\n{upgrades}",
+                        if record.seed.is_some() {
+                            "Daily Challenge\n"
+                        } else {
+                            ""
+                        },
+                        record.theme.title(),
+                        pretty_num(record.total_lines.floor()),
+                        pretty_num(record.tech_debt),
+                    ),
+                    side: TooltipSide::Top,
+                    offset: Vec2::ZERO,
+                },
+            ))
+            .set_parent(list);
+
+        for key in PastJamsSort::iter() {
+            let is_best = match key.score_idx() {
+                Some(idx) => record.scores[idx] >= best_scores[idx],
+                None => key == PastJamsSort::Rank && record.rank == best_rank,
+            };
+            commands
+                .spawn((
+                    Name::new("PastJamsCellText"),
+                    TextBundle::from_section(
+                        key.cell_text(record),
+                        TextStyle {
+                            font: if is_best {
+                                BOLD_FONT_HANDLE
+                            } else {
+                                FONT_HANDLE
+                            },
+                            color: if is_best {
+                                config.highlight_text_color
+                            } else {
+                                config.text_color
+                            },
+                            ..default()
+                        },
+                    ),
+                    FontSize::new(config.table_font_size),
+                ))
+                .set_parent(row);
+        }
+    }
+}
+
+fn spawn_return_button(commands: &mut Commands, config: &PastJamsScreenConfig) -> Entity {
+    let return_button = commands
+        .spawn((
+            Name::new("ReturnButton"),
+            ButtonBundle {
+                style: Style {
+                    margin: UiRect::top(Vh(4.0)),
+                    padding: UiRect::all(Px(16.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: config.return_button_normal_color.into(),
+                ..default()
+            },
+            InteractionPalette {
+                normal: config.return_button_normal_color,
+                hovered: config.return_button_hovered_color,
+                pressed: config.return_button_pressed_color,
+                disabled: Color::NONE,
+            },
+            On::<Pointer<Click>>::run(|mut next_state: ResMut<NextState<_>>| {
+                next_state.set(AppState::TitleScreen);
+            }),
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("ReturnButtonText"),
+            TextBundle::from_section(
+                "Back",
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: config.return_button_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.font_size),
+        ))
+        .set_parent(return_button);
+
+    return_button
+}
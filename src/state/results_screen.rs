@@ -1,24 +1,48 @@
+mod leaderboard;
+mod submission;
+
 use bevy::prelude::*;
 use bevy::ui::Val::*;
+use bevy::utils::HashMap;
 use bevy_asset_loader::prelude::*;
 use bevy_mod_picking::prelude::*;
+use rand::thread_rng;
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::config::Config;
-use crate::physics::PhysicsSettings;
-use crate::simulation::PassiveCodeTyper;
-use crate::simulation::PassiveEntitySpawner;
+use crate::history::now_timestamp;
+use crate::history::JamHistory;
+use crate::history::JamRecord;
+use crate::simulation::DailyChallenge;
+use crate::simulation::GameMode;
+use crate::simulation::GameTime;
+use crate::simulation::IncomeSource;
+use crate::simulation::JamTheme;
+use crate::simulation::RunRng;
+use crate::simulation::RunStats;
+use crate::simulation::ScoringConfig;
 use crate::simulation::Simulation;
+use crate::simulation::StarVotes;
 use crate::state::editor_screen::EditorScreenStartTime;
 use crate::state::editor_screen::UpgradeOutline;
+use crate::state::results_screen::leaderboard::CompetitorConfig;
+pub use crate::state::results_screen::leaderboard::Leaderboard;
+pub use crate::state::results_screen::submission::Submission;
+use crate::state::results_screen::submission::SubmissionScore;
+use crate::state::results_screen::submission::SubmissionUpgrade;
 use crate::state::AppState;
 use crate::state::AppState::*;
 use crate::ui::FontSize;
 use crate::ui::InteractionPalette;
+use crate::ui::Tooltip;
+use crate::ui::TooltipSide;
 use crate::ui::BOLD_FONT_HANDLE;
 use crate::ui::FONT_HANDLE;
 use crate::ui::HEADER_FONT_HANDLE;
+use crate::upgrade::UpgradeKind;
+use crate::upgrade::UpgradeList;
+use crate::util::pretty_num;
 use crate::AppRoot;
 
 pub struct ResultsScreenStatePlugin;
@@ -27,7 +51,9 @@ impl Plugin for ResultsScreenStatePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<ResultsScreenConfig>()
             .register_type::<ResultsScreenAssets>()
+            .register_type::<ResultsPanel>()
             .init_collection::<ResultsScreenAssets>()
+            .add_plugins((leaderboard::LeaderboardPlugin, submission::SubmissionPlugin))
             .add_systems(OnEnter(ResultsScreen), enter_results_screen)
             .add_systems(OnExit(ResultsScreen), exit_results_screen);
     }
@@ -48,6 +74,14 @@ pub struct ResultsScreenConfig {
     table_header_background_color: Color,
     table_header_text_color: Color,
 
+    breakdown_font_size: Val,
+
+    competitors: CompetitorConfig,
+    leaderboard_font_size: Val,
+    leaderboard_player_row_color: Color,
+
+    histogram_bar_color: Color,
+
     return_button_normal_color: Color,
     return_button_hovered_color: Color,
     return_button_pressed_color: Color,
@@ -55,7 +89,14 @@ pub struct ResultsScreenConfig {
 }
 
 const TITLE_TEXT: &str = "Results";
-const TABLE_HEADER_TEXT: [&str; 4] = ["Criteria", "Rank", "Score*", "Raw Score"];
+const TABLE_HEADER_TEXT: [&str; 6] = [
+    "Criteria",
+    "Rank",
+    "Score*",
+    "Std Dev",
+    "Raw Score",
+    "Votes",
+];
 const TABLE_CRITERIA_TEXT: [&str; 4] = ["Fun", "Presentation", "Theme Interpretation", "Overall"];
 
 #[derive(AssetCollection, Resource, Reflect, Default)]
@@ -69,10 +110,20 @@ fn enter_results_screen(
     root: Res<AppRoot>,
     config: Res<Config>,
     simulation: Res<Simulation>,
+    mode: Res<GameMode>,
+    theme: Res<JamTheme>,
+    challenge: Res<DailyChallenge>,
     start_time: Res<EditorScreenStartTime>,
-    time: Res<Time>,
+    time: Res<GameTime>,
+    stats: Res<RunStats>,
+    outline: Res<UpgradeOutline>,
+    upgrade_list: Res<UpgradeList>,
+    run_rng: Res<RunRng>,
+    mut history: ResMut<JamHistory>,
 ) {
+    let scoring = &config.scoring;
     let config = &config.results_screen;
+    let theme = mode.theme(*theme, &challenge);
     commands.insert_resource(ClearColor(config.background_color));
 
     let screen = commands
@@ -123,7 +174,9 @@ fn enter_results_screen(
                         GridTrack::auto(),
                         GridTrack::fr(1.0),
                         GridTrack::fr(1.0),
-                        GridTrack::fr(1.5),
+                        GridTrack::fr(1.0),
+                        GridTrack::fr(1.0),
+                        GridTrack::fr(1.0),
                     ],
                     ..default()
                 },
@@ -167,25 +220,27 @@ fn enter_results_screen(
             .set_parent(cell);
     }
 
-    const SUBMISSIONS: f64 = 90.0;
-    const LO: f64 = 1.5;
-    const HI: f64 = 4.8;
     let elapsed = time.elapsed_seconds_f64() - start_time.0;
-    let ratings = (elapsed / 60.0).clamp(5.0, 120.0).floor();
-    let scores: [f64; 4] = simulation.calculate_scores(ratings);
-    for (row, (&criterion, score)) in TABLE_CRITERIA_TEXT
-        .iter()
-        .zip(scores.into_iter())
-        .enumerate()
-    {
-        // Calculate rank by linearly mapping score from [LO, HI] to [1, SUBMISSIONS]
-        let rank = (1.0 - (score.clamp(LO, HI) - LO) / (HI - LO)) * (SUBMISSIONS - 1.0) + 1.0;
+    let ratings = scoring.ratings(elapsed);
+    let rated = simulation.rate(scoring, theme, elapsed, ratings, thread_rng());
+    let scores = rated.scores;
+    let title = leaderboard::random_title(thread_rng());
+    let leaderboard = Leaderboard::generate(
+        &config.competitors,
+        scoring,
+        title.clone(),
+        scores,
+        thread_rng(),
+    );
+    for (row, &criterion) in TABLE_CRITERIA_TEXT.iter().enumerate() {
+        let rank = leaderboard.rank(row, scores[row]);
 
         let entries = [
             criterion,
-            &format!("#{:.0}", rank),
-            &format!("{:.3}", score),
-            &format!("{:.3}", score),
+            &format!("#{rank}"),
+            &format!("{:.3}", scores[row]),
+            &format!("{:.3}", rated.std_devs[row]),
+            &format!("{:.3}", rated.raw_scores[row]),
         ];
         for (col, &text) in entries.iter().enumerate() {
             let cell = commands
@@ -222,6 +277,27 @@ fn enter_results_screen(
                 ))
                 .set_parent(cell);
         }
+
+        let cell = commands
+            .spawn((
+                Name::new(format!("BodyCellRow{row}Votes")),
+                NodeBundle {
+                    style: Style {
+                        padding: UiRect::all(VMin(3.0)),
+                        border: UiRect::top(config.border_width),
+                        ..default()
+                    },
+                    border_color: config.border_color.into(),
+                    ..default()
+                },
+            ))
+            .set_parent(table)
+            .id();
+        // Overall has no votes of its own
+        if let Some(&votes) = rated.votes.get(row) {
+            let histogram = spawn_histogram(&mut commands, config, votes);
+            commands.entity(histogram).set_parent(cell);
+        }
     }
 
     let hbox = commands
@@ -245,7 +321,10 @@ fn enter_results_screen(
         .spawn((
             Name::new("RankedText"),
             TextBundle::from_section(
-                format!("Ranked from {ratings:.0} ratings."),
+                format!(
+                    "Ranked from {ratings} ratings among {} entries.",
+                    leaderboard.entries.len(),
+                ),
                 TextStyle {
                     font: FONT_HANDLE,
                     color: config.text_color,
@@ -256,19 +335,414 @@ fn enter_results_screen(
         ))
         .set_parent(hbox);
 
+    for (panel, text) in [
+        (ResultsPanel::Leaderboard, "Leaderboard"),
+        (ResultsPanel::Breakdown, "Why?"),
+    ] {
+        let panel_button = spawn_panel_button(&mut commands, config, panel, text);
+        commands.entity(panel_button).set_parent(hbox);
+    }
+
+    #[cfg(not(feature = "web"))]
+    {
+        let export_button = submission::spawn_export_button(&mut commands, config);
+        commands.entity(export_button).set_parent(hbox);
+    }
+
     let return_button = spawn_return_button(&mut commands, config);
     commands.entity(return_button).set_parent(hbox);
+
+    let breakdown = spawn_breakdown(
+        &mut commands,
+        config,
+        scoring,
+        &simulation,
+        theme,
+        elapsed,
+        &stats,
+        &outline,
+        &upgrade_list,
+    );
+    commands.entity(breakdown).set_parent(screen);
+
+    let leaderboard_panel = leaderboard::spawn_leaderboard(&mut commands, config, &leaderboard);
+    commands.entity(leaderboard_panel).set_parent(screen);
+
+    let mut upgrades = outline
+        .0
+        .iter()
+        .filter(|(&kind, _)| !upgrade_list[kind].no_outline)
+        .map(|(&kind, &count)| (&upgrade_list[kind], count))
+        .collect::<Vec<_>>();
+    upgrades.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.name.cmp(&b.0.name)));
+    let timestamp = now_timestamp();
+
+    commands.insert_resource(Submission {
+        title,
+        theme: theme.title().to_string(),
+        timestamp,
+        duration: elapsed,
+        ratings,
+        entries: leaderboard.entries.len(),
+        scores: TABLE_CRITERIA_TEXT
+            .iter()
+            .zip(scores)
+            .enumerate()
+            .map(|(i, (criteria, score))| SubmissionScore {
+                criteria: criteria.to_string(),
+                rank: leaderboard.rank(i, score),
+                score,
+                std_dev: rated.std_devs[i],
+                raw_score: rated.raw_scores[i],
+            })
+            .collect(),
+        lines: simulation.lines,
+        total_lines: simulation.total_lines,
+        entities: simulation.entities,
+        tech_debt: simulation.tech_debt,
+        fun_score: simulation.fun_score,
+        presentation_score: simulation.presentation_score,
+        upgrades: upgrades
+            .iter()
+            .map(|&(upgrade, count)| SubmissionUpgrade {
+                name: upgrade.name.clone(),
+                count,
+                description: upgrade.description(),
+            })
+            .collect(),
+    });
+
+    history.push(JamRecord {
+        timestamp,
+        theme,
+        duration: elapsed,
+        scores,
+        rank: leaderboard.rank(3, scores[3]),
+        entries: leaderboard.entries.len(),
+        lines: simulation.lines,
+        total_lines: simulation.total_lines,
+        entities: simulation.entities,
+        tech_debt: simulation.tech_debt,
+        fun_score: simulation.fun_score,
+        presentation_score: simulation.presentation_score,
+        upgrades: upgrades
+            .iter()
+            .map(|&(upgrade, count)| (upgrade.name.clone(), count))
+            .collect(),
+        seed: run_rng.seed,
+    });
+
+    commands.insert_resource(leaderboard);
+}
+
+/// Spawns a bar chart of the number of votes for each star rating.
+fn spawn_histogram(
+    commands: &mut Commands,
+    config: &ResultsScreenConfig,
+    votes: StarVotes,
+) -> Entity {
+    let histogram = commands
+        .spawn((
+            Name::new("Histogram"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: config.font_size,
+                    align_items: AlignItems::End,
+                    column_gap: Percent(4.0),
+                    ..default()
+                },
+                ..default()
+            },
+            Interaction::default(),
+            Tooltip {
+                text: (1..=5)
+                    .rev()
+                    .map(|stars| format!("{stars} stars: {}", votes.0[stars - 1]))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                side: TooltipSide::Top,
+                offset: Vec2::ZERO,
+            },
+        ))
+        .id();
+
+    let max = votes.0.iter().copied().max().unwrap_or_default().max(1);
+    for count in votes.0 {
+        commands
+            .spawn((
+                Name::new("HistogramBar"),
+                NodeBundle {
+                    style: Style {
+                        flex_grow: 1.0,
+                        // Always show a sliver so empty bars stay visible
+                        height: Percent((100.0 * count as f32 / max as f32).max(4.0)),
+                        ..default()
+                    },
+                    background_color: config.histogram_bar_color.into(),
+                    ..default()
+                },
+            ))
+            .set_parent(histogram);
+    }
+
+    histogram
+}
+
+/// A panel below the results table. Only one panel is shown at a time.
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Eq)]
+enum ResultsPanel {
+    Leaderboard,
+    Breakdown,
+}
+
+fn spawn_breakdown(
+    commands: &mut Commands,
+    config: &ResultsScreenConfig,
+    scoring: &ScoringConfig,
+    simulation: &Simulation,
+    theme: JamTheme,
+    elapsed: f64,
+    stats: &RunStats,
+    outline: &UpgradeOutline,
+    upgrade_list: &UpgradeList,
+) -> Entity {
+    let breakdown = commands
+        .spawn((
+            Name::new("Breakdown"),
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    width: Percent(100.0),
+                    margin: UiRect::vertical(Vh(4.0)),
+                    column_gap: VMin(3.0),
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                ..default()
+            },
+            ResultsPanel::Breakdown,
+        ))
+        .id();
+
+    let [fun_threshold, presentation_threshold, theme_threshold] =
+        simulation.next_score_thresholds(scoring, theme, elapsed);
+    let next_threshold_text = |threshold: Option<f64>, unit: &str| {
+        threshold.map_or("Maxed out!".to_string(), |x| {
+            format!("Next +0.5 at {} {unit}.", pretty_num(x.max(0.0).ceil()))
+        })
+    };
+
+    let columns = [
+        (
+            TABLE_CRITERIA_TEXT[0],
+            format!(
+                "{}\nTotal: {} fun\n{}",
+                upgrade_breakdown_text(&stats.fun_by_upgrade, outline, upgrade_list),
+                pretty_num(simulation.fun_score),
+                next_threshold_text(fun_threshold, "fun"),
+            ),
+        ),
+        (
+            TABLE_CRITERIA_TEXT[1],
+            format!(
+                "{}{}\nTotal: {} presentation\n{}",
+                upgrade_breakdown_text(&stats.presentation_by_upgrade, outline, upgrade_list),
+                collection_bonus_text(stats.collection_bonus),
+                pretty_num(simulation.presentation_score),
+                next_threshold_text(presentation_threshold, "presentation"),
+            ),
+        ),
+        (
+            TABLE_CRITERIA_TEXT[2],
+            format!(
+                "Theme: {}\n{}\n{}",
+                theme.title(),
+                theme.input_text(simulation, elapsed),
+                theme_threshold.map_or("Maxed out!".to_string(), |x| {
+                    format!("Next +0.5 at {}.", theme.threshold_text(x))
+                }),
+            ),
+        ),
+        (
+            "Income",
+            format!(
+                "Lines:\n{}\nEntities:\n{}\nPeak typing speed: {:.0} WPM",
+                income_breakdown_text(&stats.lines_by_source, upgrade_list),
+                income_breakdown_text(&stats.entities_by_source, upgrade_list),
+                stats.peak_wpm,
+            ),
+        ),
+    ];
+
+    for (header, body) in columns {
+        let column = commands
+            .spawn((
+                Name::new("BreakdownColumn"),
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Vh(1.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .set_parent(breakdown)
+            .id();
+
+        commands
+            .spawn((
+                Name::new("BreakdownHeader"),
+                TextBundle::from_section(
+                    header,
+                    TextStyle {
+                        font: BOLD_FONT_HANDLE,
+                        color: config.hyperlink_text_color,
+                        ..default()
+                    },
+                ),
+                FontSize::new(config.breakdown_font_size),
+            ))
+            .set_parent(column);
+
+        commands
+            .spawn((
+                Name::new("BreakdownText"),
+                TextBundle::from_section(
+                    body,
+                    TextStyle {
+                        font: FONT_HANDLE,
+                        color: config.text_color,
+                        ..default()
+                    },
+                ),
+                FontSize::new(config.breakdown_font_size),
+            ))
+            .set_parent(column);
+    }
+
+    breakdown
+}
+
+fn upgrade_breakdown_text(
+    score_by_upgrade: &HashMap<UpgradeKind, f64>,
+    outline: &UpgradeOutline,
+    upgrade_list: &UpgradeList,
+) -> String {
+    if score_by_upgrade.is_empty() {
+        return "No upgrades.\n".to_string();
+    }
+
+    let mut text = String::new();
+    for (kind, score) in RunStats::ranked(score_by_upgrade) {
+        let upgrade = &upgrade_list[kind];
+        let count = outline.0.get(&kind).copied().unwrap_or(0);
+        let count = if !upgrade.no_count && count >= 2 {
+            format!(" ({count})")
+        } else {
+            String::new()
+        };
+        text.push_str(&format!(
+            "{}{count}: +{}\n",
+            upgrade.name,
+            pretty_num(score)
+        ));
+    }
+    text
+}
+
+fn collection_bonus_text(bonus: f64) -> String {
+    if bonus == 0.0 {
+        return String::new();
+    }
+    format!("Skin collection: +{}\n", pretty_num(bonus))
+}
+
+/// The number of sources to list in the income breakdown.
+const INCOME_SOURCES_SHOWN: usize = 5;
+
+fn income_breakdown_text(
+    count_by_source: &HashMap<IncomeSource, f64>,
+    upgrade_list: &UpgradeList,
+) -> String {
+    let mut text = String::new();
+    for (source, count) in RunStats::ranked(count_by_source)
+        .into_iter()
+        .filter(|&(_, count)| count >= 1.0)
+        .take(INCOME_SOURCES_SHOWN)
+    {
+        text.push_str(&format!(
+            "{}: {}\n",
+            source.label(upgrade_list),
+            pretty_num(count.floor())
+        ));
+    }
+    if text.is_empty() {
+        text.push_str("None.\n");
+    }
+    text
+}
+
+fn spawn_panel_button(
+    commands: &mut Commands,
+    config: &ResultsScreenConfig,
+    panel: ResultsPanel,
+    text: &str,
+) -> Entity {
+    let panel_button = commands
+        .spawn((
+            Name::new("PanelButton"),
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::all(Px(16.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: config.return_button_normal_color.into(),
+                ..default()
+            },
+            InteractionPalette {
+                normal: config.return_button_normal_color,
+                hovered: config.return_button_hovered_color,
+                pressed: config.return_button_pressed_color,
+                disabled: Color::NONE,
+            },
+            On::<Pointer<Click>>::run(
+                move |mut panel_query: Query<(&ResultsPanel, &mut Style)>| {
+                    for (&other, mut style) in &mut panel_query {
+                        style.display = if other == panel {
+                            Display::Flex
+                        } else {
+                            Display::None
+                        };
+                    }
+                },
+            ),
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("PanelButtonText"),
+            TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: config.return_button_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.font_size),
+        ))
+        .set_parent(panel_button);
+
+    panel_button
 }
 
 fn exit_results_screen(mut commands: Commands, root: Res<AppRoot>) {
     commands.entity(root.ui).despawn_descendants();
-
-    // Reset resources so replaying works
-    commands.insert_resource(Simulation::default());
-    commands.insert_resource(PhysicsSettings::default());
-    commands.insert_resource(PassiveCodeTyper::default());
-    commands.insert_resource(PassiveEntitySpawner::default());
-    commands.insert_resource(UpgradeOutline::default());
 }
 
 fn spawn_return_button(commands: &mut Commands, config: &ResultsScreenConfig) -> Entity {
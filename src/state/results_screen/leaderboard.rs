@@ -0,0 +1,398 @@
+use bevy::prelude::*;
+use bevy::ui::Val::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::simulation::ScoringConfig;
+use crate::simulation::StarVotes;
+use crate::state::results_screen::ResultsPanel;
+use crate::state::results_screen::ResultsScreenConfig;
+use crate::ui::FontSize;
+use crate::ui::ScrollContent;
+use crate::ui::BOLD_FONT_HANDLE;
+use crate::ui::FONT_HANDLE;
+use crate::util::gen_normal;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CompetitorConfig>()
+            .register_type::<Leaderboard>()
+            .init_resource::<Leaderboard>();
+    }
+}
+
+#[derive(Default, Reflect, Serialize, Deserialize)]
+pub struct CompetitorConfig {
+    /// The number of other entries in the jam.
+    count: usize,
+    /// The range of ratings each entry receives.
+    min_ratings: f64,
+    max_ratings: f64,
+    /// The mean score for Fun, Presentation, and Theme Interpretation.
+    score_mean: [f64; 3],
+    /// The standard deviation of the score for Fun, Presentation, and Theme Interpretation.
+    score_std_dev: [f64; 3],
+    /// How closely an entry's scores follow each other (between 0 and 1).
+    score_correlation: f64,
+}
+
+const NAME_PREFIXES: [&str; 16] = [
+    "pixel", "ferris", "rusty", "cosmic", "tiny", "lazy", "quantum", "cozy", "bit", "retro",
+    "sleepy", "turbo", "mossy", "neon", "grumpy", "jolly",
+];
+const NAME_SUFFIXES: [&str; 12] = [
+    "_dev", "crab", "games", "wizard", "studio", "coder", "bytes", "fox", "_jams", "forge", "cat",
+    "42",
+];
+const TITLE_ADJECTIVES: [&str; 16] = [
+    "Infinite",
+    "Tiny",
+    "Crabby",
+    "Endless",
+    "Cursed",
+    "Cozy",
+    "Lost",
+    "Quantum",
+    "Sticky",
+    "Haunted",
+    "Bouncy",
+    "Last",
+    "Forgotten",
+    "Angry",
+    "Hungry",
+    "Super",
+];
+const TITLE_NOUNS: [&str; 16] = [
+    "Swarm",
+    "Kingdom",
+    "Entities",
+    "Garden",
+    "Factory",
+    "Dungeon",
+    "Horde",
+    "Colony",
+    "Orbit",
+    "Crabs",
+    "Bees",
+    "Slimes",
+    "Particles",
+    "Tower",
+    "Planet",
+    "Queue",
+];
+
+/// Generates a random game title, like "Cozy Crabs".
+pub fn random_title(mut rng: impl Rng) -> String {
+    format!(
+        "{} {}",
+        TITLE_ADJECTIVES.choose(&mut rng).unwrap(),
+        TITLE_NOUNS.choose(&mut rng).unwrap(),
+    )
+}
+
+#[derive(Reflect, Clone, Default)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub title: String,
+    /// The mean vote for each category, in the same order as `Ratings::scores`.
+    pub scores: [f64; 4],
+    pub is_player: bool,
+}
+
+impl LeaderboardEntry {
+    fn random(config: &CompetitorConfig, scoring: &ScoringConfig, mut rng: impl Rng) -> Self {
+        let name = format!(
+            "{}{}",
+            NAME_PREFIXES.choose(&mut rng).unwrap(),
+            NAME_SUFFIXES.choose(&mut rng).unwrap(),
+        );
+        let title = random_title(&mut rng);
+
+        // Correlate the scores through a shared "quality" of the entry
+        let min_ratings = config.min_ratings.max(0.0);
+        let max_ratings = config.max_ratings.max(min_ratings);
+        let ratings = rng.gen_range(min_ratings..=max_ratings).floor() as usize;
+        let quality = gen_normal(&mut rng);
+        let correlation = config.score_correlation.clamp(0.0, 1.0);
+        let mut scores = [0.0; 3];
+        for (score, (mean, std_dev)) in scores
+            .iter_mut()
+            .zip(config.score_mean.iter().zip(&config.score_std_dev))
+        {
+            let z = correlation * quality
+                + (1.0 - correlation * correlation).sqrt() * gen_normal(&mut rng);
+            // Rate the entry with the same simulated raters as the player's entry
+            let votes =
+                StarVotes::simulate(mean + std_dev * z, ratings, scoring.rater_noise, &mut rng);
+            *score = votes.mean();
+        }
+        let [fun, presentation, theme] = scores;
+        let scores = [fun, presentation, theme, scoring.overall(scores)];
+
+        Self {
+            name,
+            title,
+            scores,
+            is_player: false,
+        }
+    }
+}
+
+/// The player's entry ranked among a procedurally generated field of competitors.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct Leaderboard {
+    /// All entries sorted by Overall score, from best to worst.
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn generate(
+        config: &CompetitorConfig,
+        scoring: &ScoringConfig,
+        player_title: String,
+        player_scores: [f64; 4],
+        mut rng: impl Rng,
+    ) -> Self {
+        let mut entries = vec![LeaderboardEntry {
+            name: "You".to_string(),
+            title: player_title,
+            scores: player_scores,
+            is_player: true,
+        }];
+        entries
+            .extend((0..config.count).map(|_| LeaderboardEntry::random(config, scoring, &mut rng)));
+        entries.sort_by(|a, b| b.scores[3].total_cmp(&a.scores[3]));
+
+        Self { entries }
+    }
+
+    /// Returns the rank (starting from 1) of the given score in the given category.
+    /// Entries with the same score share the same rank.
+    pub fn rank(&self, category: usize, score: f64) -> usize {
+        // Allow for rounding error between scores with a different number of ratings
+        const EPSILON: f64 = 1e-9;
+        1 + self
+            .entries
+            .iter()
+            .filter(|entry| entry.scores[category] > score + EPSILON)
+            .count()
+    }
+}
+
+pub fn spawn_leaderboard(
+    commands: &mut Commands,
+    config: &ResultsScreenConfig,
+    leaderboard: &Leaderboard,
+) -> Entity {
+    let leaderboard_panel = commands
+        .spawn((
+            Name::new("Leaderboard"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_grow: 1.0,
+                    margin: UiRect::vertical(Vh(4.0)),
+                    border: UiRect::all(config.border_width),
+                    ..default()
+                },
+                border_color: config.border_color.into(),
+                ..default()
+            },
+            ResultsPanel::Leaderboard,
+        ))
+        .id();
+
+    let scroll_view = commands
+        .spawn((
+            Name::new("LeaderboardScrollView"),
+            NodeBundle {
+                style: Style {
+                    // Restrict height to the available space in the parent node
+                    position_type: PositionType::Absolute,
+                    overflow: Overflow::clip_y(),
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    padding: UiRect::right(Px(12.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .set_parent(leaderboard_panel)
+        .id();
+
+    let scrollbar = commands
+        .spawn((
+            Name::new("LeaderboardScrollbar"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::ZERO,
+                    width: Px(8.0),
+                    height: Percent(100.0),
+                    ..default()
+                },
+                background_color: config.table_header_background_color.into(),
+                ..default()
+            },
+        ))
+        .set_parent(leaderboard_panel)
+        .id();
+
+    let container = commands
+        .spawn((
+            Name::new("LeaderboardContainer"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            ScrollContent {
+                position: 0.0,
+                sensitivity: 1.5,
+                scrollbar,
+            },
+        ))
+        .set_parent(scroll_view)
+        .id();
+
+    let header = ["Rank", "Entry", "Fun", "Pres.", "Theme", "Overall"].map(String::from);
+    let header_row = spawn_leaderboard_row(commands, config, header, true);
+    commands
+        .entity(header_row)
+        .insert(BackgroundColor(config.table_header_background_color))
+        .set_parent(container);
+
+    for entry in &leaderboard.entries {
+        let [fun, presentation, theme, overall] = entry.scores;
+        let row = spawn_leaderboard_row(
+            commands,
+            config,
+            [
+                format!("#{}", leaderboard.rank(3, overall)),
+                format!("{} by {}", entry.title, entry.name),
+                format!("{fun:.2}"),
+                format!("{presentation:.2}"),
+                format!("{theme:.2}"),
+                format!("{overall:.3}"),
+            ],
+            entry.is_player,
+        );
+        if entry.is_player {
+            commands
+                .entity(row)
+                .insert(BackgroundColor(config.leaderboard_player_row_color));
+        }
+        commands.entity(row).set_parent(container);
+    }
+
+    leaderboard_panel
+}
+
+fn spawn_leaderboard_row(
+    commands: &mut Commands,
+    config: &ResultsScreenConfig,
+    cells: [String; 6],
+    bold: bool,
+) -> Entity {
+    let row = commands
+        .spawn((
+            Name::new("LeaderboardRow"),
+            NodeBundle {
+                style: Style {
+                    display: Display::Grid,
+                    width: Percent(100.0),
+                    padding: UiRect::axes(VMin(2.0), VMin(0.8)),
+                    column_gap: VMin(2.0),
+                    grid_template_columns: vec![
+                        GridTrack::fr(0.6),
+                        GridTrack::fr(4.0),
+                        GridTrack::fr(1.0),
+                        GridTrack::fr(1.0),
+                        GridTrack::fr(1.0),
+                        GridTrack::fr(1.0),
+                    ],
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
+    for text in cells {
+        commands
+            .spawn((
+                Name::new("LeaderboardCellText"),
+                TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font: if bold { BOLD_FONT_HANDLE } else { FONT_HANDLE },
+                        color: config.text_color,
+                        ..default()
+                    },
+                ),
+                FontSize::new(config.leaderboard_font_size),
+            ))
+            .set_parent(row);
+    }
+
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn rank_test() {
+        let leaderboard = Leaderboard {
+            entries: [4.0, 3.5, 3.5, 2.0]
+                .into_iter()
+                .map(|overall| LeaderboardEntry {
+                    scores: [0.0, 0.0, 0.0, overall],
+                    ..default()
+                })
+                .collect(),
+        };
+
+        // Best
+        assert_eq!(leaderboard.rank(3, 4.0), 1);
+        // Ties share the best rank
+        assert_eq!(leaderboard.rank(3, 3.5), 2);
+        // Rounding error is still a tie
+        assert_eq!(leaderboard.rank(3, 3.5 - 1e-12), 2);
+        assert_eq!(leaderboard.rank(3, 3.5 + 1e-12), 2);
+        // Rank after the tie skips the shared ranks
+        assert_eq!(leaderboard.rank(3, 2.0), 4);
+        // Worse than every entry
+        assert_eq!(leaderboard.rank(3, 1.0), 5);
+    }
+
+    #[test]
+    fn random_entry_with_inverted_ratings_test() {
+        let config = CompetitorConfig {
+            min_ratings: 50.0,
+            max_ratings: 10.0,
+            score_mean: [3.0; 3],
+            ..default()
+        };
+        let scoring = ScoringConfig::default();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // Uses the minimum instead of panicking
+        let entry = LeaderboardEntry::random(&config, &scoring, &mut rng);
+        assert_eq!(entry.scores, [3.0; 4]);
+    }
+}
@@ -0,0 +1,62 @@
+// Exporting writes files, so it's not available on web
+#[cfg(not(feature = "web"))]
+mod export;
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+#[cfg(not(feature = "web"))]
+pub use crate::state::results_screen::submission::export::spawn_export_button;
+
+pub struct SubmissionPlugin;
+
+impl Plugin for SubmissionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Submission>()
+            .init_resource::<Submission>();
+
+        #[cfg(not(feature = "web"))]
+        app.add_plugins(export::ExportPlugin);
+    }
+}
+
+#[derive(Reflect, Clone, Default, Serialize)]
+pub struct SubmissionScore {
+    pub criteria: String,
+    pub rank: usize,
+    /// The mean vote.
+    pub score: f64,
+    pub std_dev: f64,
+    /// The calculated score before rater noise.
+    pub raw_score: f64,
+}
+
+#[derive(Reflect, Clone, Default, Serialize)]
+pub struct SubmissionUpgrade {
+    pub name: String,
+    pub count: usize,
+    pub description: String,
+}
+
+/// A jam submission page for the finished run.
+#[derive(Resource, Reflect, Clone, Default, Serialize)]
+#[reflect(Resource)]
+pub struct Submission {
+    pub title: String,
+    /// The title of the jam theme.
+    pub theme: String,
+    /// Seconds since the Unix epoch when the entry was submitted.
+    pub timestamp: u64,
+    /// Seconds of game time spent in the editor.
+    pub duration: f64,
+    pub ratings: usize,
+    pub entries: usize,
+    pub scores: Vec<SubmissionScore>,
+    pub lines: f64,
+    pub total_lines: f64,
+    pub entities: f64,
+    pub tech_debt: f64,
+    pub fun_score: f64,
+    pub presentation_score: f64,
+    pub upgrades: Vec<SubmissionUpgrade>,
+}
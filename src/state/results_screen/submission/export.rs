@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+use bevy::ui::Val::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::state::results_screen::ResultsScreenConfig;
+use crate::state::results_screen::Submission;
+use crate::ui::FontSize;
+use crate::ui::InteractionPalette;
+use crate::ui::HEADER_FONT_HANDLE;
+use crate::util::pretty_num;
+
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<IsExportButtonText>();
+    }
+}
+
+/// The directory that submission pages are exported to.
+const SUBMISSIONS_DIR: &str = "submissions";
+
+impl Submission {
+    /// Assembles a description of the game from its installed upgrades.
+    pub fn description(&self) -> String {
+        if self.upgrades.is_empty() {
+            return "A game about entities. Just entities.".to_string();
+        }
+
+        let mut description = format!(
+            "{} is a game about {} entities, built with {} upgrades:\n\n",
+            self.title,
+            pretty_num(self.entities.floor()),
+            self.upgrades.iter().map(|x| x.count).sum::<usize>(),
+        );
+        for upgrade in &self.upgrades {
+            let count = if upgrade.count >= 2 {
+                format!(" (x{})", upgrade.count)
+            } else {
+                String::new()
+            };
+            description.push_str(&format!(
+                "- **{}**{count}: {}\n",
+                upgrade.name, upgrade.description,
+            ));
+        }
+        description
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "# {}\n\n*Theme: {}*\n\n{}\n",
+            self.title,
+            self.theme,
+            self.description(),
+        );
+
+        markdown.push_str(&format!(
+            "\n## Results\n\nRanked from {} ratings among {} entries.\n\n",
+            self.ratings, self.entries,
+        ));
+        markdown.push_str(
+            "| Criteria | Rank | Score | Std Dev | Raw Score |\n| --- | --- | --- | --- | --- |\n",
+        );
+        for score in &self.scores {
+            markdown.push_str(&format!(
+                "| {} | #{} | {:.3} | {:.3} | {:.3} |\n",
+                score.criteria, score.rank, score.score, score.std_dev, score.raw_score,
+            ));
+        }
+
+        let seconds = self.duration.max(0.0) as u64;
+        markdown.push_str(&format!(
+            "\n## Stats\n\n\
+            - Development time: {}:{:02}\n\
+            - Lines of code: {}\n\
+            - Total lines written: {}\n\
+            - Entities: {}\n\
+            - Technical debt: {}\n\
+            - Fun: {}\n\
+            - Presentation: {}\n",
+            seconds / 60,
+            seconds % 60,
+            pretty_num(self.lines.floor()),
+            pretty_num(self.total_lines.floor()),
+            pretty_num(self.entities.floor()),
+            pretty_num(self.tech_debt),
+            pretty_num(self.fun_score),
+            pretty_num(self.presentation_score),
+        ));
+
+        markdown
+    }
+
+    /// Writes the submission page as Markdown and JSON, returning the path without extension.
+    fn export(&self) -> std::io::Result<String> {
+        let slug = self
+            .title
+            .to_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("_");
+        let path = format!("{SUBMISSIONS_DIR}/{}_{slug}", self.timestamp);
+
+        std::fs::create_dir_all(SUBMISSIONS_DIR)?;
+        std::fs::write(format!("{path}.md"), self.to_markdown())?;
+        std::fs::write(format!("{path}.json"), serde_json::to_string_pretty(self)?)?;
+
+        Ok(path)
+    }
+}
+
+#[derive(Component, Reflect)]
+struct IsExportButtonText;
+
+pub fn spawn_export_button(commands: &mut Commands, config: &ResultsScreenConfig) -> Entity {
+    let export_button = commands
+        .spawn((
+            Name::new("ExportButton"),
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::all(Px(16.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: config.return_button_normal_color.into(),
+                ..default()
+            },
+            InteractionPalette {
+                normal: config.return_button_normal_color,
+                hovered: config.return_button_hovered_color,
+                pressed: config.return_button_pressed_color,
+                disabled: Color::NONE,
+            },
+            On::<Pointer<Click>>::run(export_submission),
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("ExportButtonText"),
+            TextBundle::from_section(
+                "Export",
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: config.return_button_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.font_size),
+            IsExportButtonText,
+        ))
+        .set_parent(export_button);
+
+    export_button
+}
+
+fn export_submission(
+    submission: Res<Submission>,
+    mut text_query: Query<&mut Text, With<IsExportButtonText>>,
+) {
+    let status = match submission.export() {
+        Ok(path) => {
+            info!("Exported submission page to {path}.md");
+            "Exported!"
+        },
+        Err(e) => {
+            error!("Exporting submission page: {e}");
+            "Export failed"
+        },
+    };
+    for mut text in &mut text_query {
+        text.sections[0].value = status.to_string();
+    }
+}
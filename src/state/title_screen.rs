@@ -3,16 +3,23 @@ use bevy::ui::Val::*;
 use bevy_asset_loader::prelude::*;
 use bevy_mod_picking::prelude::*;
 use iyes_progress::prelude::*;
+use rand::thread_rng;
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::audio::AudioAssets;
 use crate::config::Config;
+use crate::history::JamHistory;
+use crate::simulation::DailyChallenge;
+use crate::simulation::GameMode;
+use crate::simulation::JamTheme;
 use crate::simulation::SpritePackAssets;
 use crate::state::editor_screen::EditorScreenAssets;
 use crate::state::AppState::*;
 use crate::ui::FontSize;
 use crate::ui::InteractionPalette;
+use crate::ui::Tooltip;
+use crate::ui::TooltipSide;
 use crate::ui::BOLD_FONT_HANDLE;
 use crate::ui::FONT_HANDLE;
 use crate::ui::HEADER_FONT_HANDLE;
@@ -24,6 +31,7 @@ impl Plugin for TitleScreenStatePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<TitleScreenConfig>()
             .register_type::<TitleScreenAssets>()
+            .register_type::<IsThemeText>()
             .init_collection::<TitleScreenAssets>()
             .add_loading_state(LoadingState::new(TitleScreen))
             .add_collection_to_loading_state::<_, EditorScreenAssets>(TitleScreen)
@@ -31,7 +39,12 @@ impl Plugin for TitleScreenStatePlugin {
             .add_collection_to_loading_state::<_, AudioAssets>(TitleScreen)
             .add_plugins(ProgressPlugin::new(TitleScreen))
             .add_systems(OnEnter(TitleScreen), enter_title_screen)
-            .add_systems(OnExit(TitleScreen), exit_title_screen);
+            .add_systems(OnExit(TitleScreen), exit_title_screen)
+            .add_systems(
+                Update,
+                update_theme_text
+                    .run_if(in_state(TitleScreen).and_then(resource_changed::<JamTheme>())),
+            );
     }
 }
 
@@ -53,6 +66,7 @@ pub struct TitleScreenConfig {
     button_pressed_color: Color,
     button_text_color: Color,
     button_font_size: Val,
+    secondary_button_font_size: Val,
 }
 
 const TITLE_TEXT: &str = "Bevy Jam Simulator";
@@ -71,7 +85,8 @@ const BODY_TEXT: [[&str; 4]; 4] = [
         "",
         "",
     ],
-    ["The theme is: ", "", "", "That's a LOT of entities!"],
+    // The theme is filled in from `JamTheme::title`
+    ["The theme is: ", "", "", ""],
 ];
 
 #[derive(AssetCollection, Resource, Reflect, Default)]
@@ -80,7 +95,16 @@ pub struct TitleScreenAssets {
     // TODO: Music / SFX maybe
 }
 
-fn enter_title_screen(mut commands: Commands, root: Res<AppRoot>, config: Res<Config>) {
+#[derive(Component, Reflect)]
+struct IsThemeText;
+
+fn enter_title_screen(
+    mut commands: Commands,
+    root: Res<AppRoot>,
+    config: Res<Config>,
+    theme: Res<JamTheme>,
+    history: Res<JamHistory>,
+) {
     let config = &config.title_screen;
     commands.insert_resource(ClearColor(config.background_color));
 
@@ -179,8 +203,13 @@ fn enter_title_screen(mut commands: Commands, root: Res<AppRoot>, config: Res<Co
         .id();
 
     // bevy_text cringe
-    for (i, line) in BODY_TEXT.into_iter().enumerate() {
-        commands
+    for (i, mut line) in BODY_TEXT.into_iter().enumerate() {
+        let is_theme_line = i == BODY_TEXT.len() - 1;
+        if is_theme_line {
+            line[3] = theme.title();
+        }
+
+        let line_text = commands
             .spawn((
                 Name::new(format!("BodyTextLine{i}")),
                 TextBundle::from_sections([
@@ -219,7 +248,12 @@ fn enter_title_screen(mut commands: Commands, root: Res<AppRoot>, config: Res<Co
                 ]),
                 FontSize::new(config.font_size),
             ))
-            .set_parent(body_container);
+            .set_parent(body_container)
+            .id();
+
+        if is_theme_line {
+            commands.entity(line_text).insert(IsThemeText);
+        }
     }
 
     let join_button = commands
@@ -243,16 +277,7 @@ fn enter_title_screen(mut commands: Commands, root: Res<AppRoot>, config: Res<Co
                 pressed: config.button_pressed_color,
                 disabled: Color::NONE,
             },
-            On::<Pointer<Click>>::run(
-                |mut next_state: ResMut<NextState<_>>, progress: Res<ProgressCounter>| {
-                    let Progress { done, total } = progress.progress_complete();
-                    next_state.set(if done >= total {
-                        EditorScreen
-                    } else {
-                        LoadingScreen
-                    });
-                },
-            ),
+            start_on_click(GameMode::Jam),
         ))
         .set_parent(container)
         .id();
@@ -271,6 +296,159 @@ fn enter_title_screen(mut commands: Commands, root: Res<AppRoot>, config: Res<Co
             FontSize::new(config.button_font_size),
         ))
         .set_parent(join_button);
+
+    let secondary_buttons = commands
+        .spawn((
+            Name::new("SecondaryButtons"),
+            NodeBundle {
+                style: Style {
+                    width: config.button_width,
+                    margin: UiRect::top(Vh(2.0)),
+                    justify_content: JustifyContent::Center,
+                    column_gap: VMin(2.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .set_parent(container)
+        .id();
+
+    let buttons = [
+        (
+            "Change Theme",
+            On::<Pointer<Click>>::run(|mut theme: ResMut<JamTheme>| {
+                *theme = theme.next();
+            }),
+        ),
+        (
+            "Random Theme",
+            On::<Pointer<Click>>::run(|mut theme: ResMut<JamTheme>| {
+                *theme = theme.random(thread_rng());
+            }),
+        ),
+        ("Daily", start_on_click(GameMode::DailyChallenge)),
+        ("Sandbox", start_on_click(GameMode::Sandbox)),
+        (
+            "Past Jams",
+            On::<Pointer<Click>>::run(|mut next_state: ResMut<NextState<_>>| {
+                next_state.set(PastJamsScreen);
+            }),
+        ),
+        (
+            "Collection",
+            On::<Pointer<Click>>::run(|mut next_state: ResMut<NextState<_>>| {
+                next_state.set(CollectionScreen);
+            }),
+        ),
+    ];
+    for (text, on_click) in buttons {
+        let button = spawn_secondary_button(&mut commands, config, text, on_click);
+        commands.entity(button).set_parent(secondary_buttons);
+        if text == "Daily" {
+            commands
+                .entity(button)
+                .insert(daily_challenge_tooltip(&DailyChallenge::today(), &history));
+        }
+    }
+}
+
+fn daily_challenge_tooltip(challenge: &DailyChallenge, history: &JamHistory) -> Tooltip {
+    let modifiers = challenge
+        .modifiers
+        .iter()
+        .map(|modifier| format!("- {}", modifier.label()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let best = match history.best_seeded_score(challenge.seed) {
+        Some(score) => format!("{score:.3}"),
+        None => "None yet".to_string(),
+    };
+
+    Tooltip {
+        text: format!(
+            "Daily Challenge ({})\nTheme: {}\n{modifiers}\n\nBest Overall: {best}",
+            challenge.date(),
+            challenge.theme.title(),
+        ),
+        side: TooltipSide::Top,
+        offset: Vec2::ZERO,
+    }
+}
+
+/// Starts the game in the given mode once loading is done.
+fn start_on_click(mode: GameMode) -> On<Pointer<Click>> {
+    On::<Pointer<Click>>::run(
+        move |mut next_state: ResMut<NextState<_>>,
+              mut game_mode: ResMut<GameMode>,
+              mut challenge: ResMut<DailyChallenge>,
+              progress: Res<ProgressCounter>| {
+            *game_mode = mode;
+            if mode == GameMode::DailyChallenge {
+                *challenge = DailyChallenge::today();
+            }
+            let Progress { done, total } = progress.progress_complete();
+            next_state.set(if done >= total {
+                EditorScreen
+            } else {
+                LoadingScreen
+            });
+        },
+    )
+}
+
+fn spawn_secondary_button(
+    commands: &mut Commands,
+    config: &TitleScreenConfig,
+    text: &str,
+    on_click: On<Pointer<Click>>,
+) -> Entity {
+    let secondary_button = commands
+        .spawn((
+            Name::new("SecondaryButton"),
+            ButtonBundle {
+                style: Style {
+                    flex_grow: 1.0,
+                    padding: UiRect::all(VMin(1.5)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: config.button_normal_color.into(),
+                ..default()
+            },
+            InteractionPalette {
+                normal: config.button_normal_color,
+                hovered: config.button_hovered_color,
+                pressed: config.button_pressed_color,
+                disabled: Color::NONE,
+            },
+            on_click,
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("SecondaryButtonText"),
+            TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: HEADER_FONT_HANDLE,
+                    color: config.button_text_color,
+                    ..default()
+                },
+            ),
+            FontSize::new(config.secondary_button_font_size),
+        ))
+        .set_parent(secondary_button);
+
+    secondary_button
+}
+
+fn update_theme_text(theme: Res<JamTheme>, mut text_query: Query<&mut Text, With<IsThemeText>>) {
+    for mut text in &mut text_query {
+        text.sections[3].value = theme.title().to_string();
+    }
 }
 
 fn exit_title_screen(mut commands: Commands, root: Res<AppRoot>) {
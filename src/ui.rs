@@ -2,18 +2,24 @@ mod code_typer;
 mod font;
 mod interaction_palette;
 mod scroll;
+mod syntax;
 mod tooltip;
 
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
 
+pub use crate::ui::code_typer::AccuracyMode;
+pub use crate::ui::code_typer::CodeTabs;
 pub use crate::ui::code_typer::CodeTyper;
+pub use crate::ui::code_typer::CodeTyperConfig;
 pub use crate::ui::font::FontSize;
 pub use crate::ui::font::BOLD_FONT_HANDLE;
 pub use crate::ui::font::FONT_HANDLE;
 pub use crate::ui::font::HEADER_FONT_HANDLE;
 pub use crate::ui::interaction_palette::InteractionPalette;
 pub use crate::ui::scroll::ScrollContent;
+pub use crate::ui::syntax::SyntaxColors;
+pub use crate::ui::syntax::SyntaxHighlighter;
 pub use crate::ui::tooltip::Tooltip;
 pub use crate::ui::tooltip::TooltipConfig;
 pub use crate::ui::tooltip::TooltipSide;
@@ -1,79 +1,409 @@
-use std::iter::Cycle;
+use std::collections::VecDeque;
 use std::str::Chars;
+use std::sync::Arc;
 
 use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
 
+use crate::audio::AudioAssets;
+use crate::audio::SoundEffectKind;
+use crate::config::Config;
+use crate::simulation::CompilerErrors;
+use crate::simulation::GameTime;
+use crate::simulation::IncomeSource;
 use crate::simulation::LinesAddedEvent;
+use crate::simulation::PassiveCodeTyper;
+use crate::simulation::Simulation;
+use crate::simulation::TypingSpeed;
+use crate::ui::SyntaxHighlighter;
 use crate::AppSet;
 
 pub struct CodeTyperPlugin;
 
 impl Plugin for CodeTyperPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<CodeTyper>()
-            .add_systems(Update, type_code.in_set(AppSet::Input));
+        app.register_type::<CodeTyperConfig>()
+            .register_type::<CodeTyper>()
+            .register_type::<AccuracyMode>()
+            .register_type::<CodeTabs>()
+            .init_resource::<CodeSources>()
+            .init_resource::<AccuracyMode>()
+            .init_resource::<CodeTabs>()
+            .add_systems(Startup, load_code_sources)
+            .add_systems(
+                Update,
+                (apply_code_sources, type_code)
+                    .chain()
+                    .in_set(AppSet::Input),
+            )
+            .add_systems(Update, update_typing_overlay.in_set(AppSet::End));
     }
 }
 
-const FILLER_CODE: &str = concat!(
-    " to generate lines of code!
+#[derive(Default, Reflect, Serialize, Deserialize)]
+pub struct CodeTyperConfig {
+    /// A directory to walk recursively for source files instead of the bundled ones (native only).
+    directory: Option<String>,
+    /// The file extensions to type from `directory`.
+    extensions: Vec<String>,
+    /// The maximum number of files to type from `directory`.
+    max_files: usize,
+
+    /// The combo multiplier gained per correct key in accuracy mode.
+    combo_bonus: f64,
+    /// The maximum combo multiplier.
+    combo_max: f64,
+    /// The technical debt added per typo in accuracy mode.
+    typo_tech_debt: f64,
+    /// The number of upcoming characters to show in accuracy mode.
+    preview_chars: usize,
+}
+
+/// Whether keys must match the code being typed.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct AccuracyMode(pub bool);
+
+/// The code files open as tabs in the code panel.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct CodeTabs {
+    pub tabs: Vec<CodeTab>,
+    /// The index of the tab that typing goes into.
+    pub focused: usize,
+}
+
+impl Default for CodeTabs {
+    fn default() -> Self {
+        Self {
+            tabs: vec![CodeTab {
+                name: "main.rs".to_string(),
+                lines: 0.0,
+            }],
+            focused: 0,
+        }
+    }
+}
+
+impl CodeTabs {
+    /// Opens a new tab with the given file name, unless it's already open.
+    pub fn open(&mut self, name: &str) {
+        if self.tabs.iter().all(|tab| tab.name != name) {
+            self.tabs.push(CodeTab {
+                name: name.to_string(),
+                lines: 0.0,
+            });
+        }
+    }
+}
+
+#[derive(Reflect)]
+pub struct CodeTab {
+    pub name: String,
+    /// The number of lines typed in this file.
+    pub lines: f64,
+}
+
+const INTRO_CODE: &str = " to generate lines of code!
 // Click to spawn entities after installing TouchOfLifePlugin.
 
-",
-    include_str!("code_typer.rs"),
-);
+";
+
+const FILLER_CODE: &str = include_str!("code_typer.rs");
+
+/// The source files to type by default, built into the binary so that they work on web and from
+/// any working directory.
+const BUNDLED_FILES: [(&str, &str); 2] = [
+    ("code/main.rs", include_str!("../../assets/code/main.rs")),
+    (
+        "code/player.rs",
+        include_str!("../../assets/code/player.rs"),
+    ),
+];
 
-// Newtype so that CodeTyper can derive Reflect
-pub struct CodeGenerator(Cycle<Chars<'static>>);
+/// The name shown while typing the code generated from the current run.
+const GENERATED_FILE_NAME: &str = "game.rs";
+
+pub struct CodeFile {
+    pub name: String,
+    pub text: String,
+}
+
+/// The source files that code typers cycle through.
+#[derive(Resource, Clone)]
+pub struct CodeSources(Arc<[CodeFile]>);
+
+impl Default for CodeSources {
+    fn default() -> Self {
+        Self(Arc::new([CodeFile {
+            name: "code_typer.rs".to_string(),
+            text: FILLER_CODE.to_string(),
+        }]))
+    }
+}
+
+impl CodeSources {
+    fn load(config: &CodeTyperConfig) -> Vec<CodeFile> {
+        let files = Self::load_directory(config);
+        if !files.is_empty() {
+            return files;
+        }
+
+        BUNDLED_FILES
+            .iter()
+            .map(|&(name, text)| CodeFile {
+                name: name.to_string(),
+                text: text.to_string(),
+            })
+            .filter(|file| !file.text.trim().is_empty())
+            .collect()
+    }
+
+    #[cfg(not(feature = "web"))]
+    fn load_directory(config: &CodeTyperConfig) -> Vec<CodeFile> {
+        use std::path::Path;
+
+        let Some(directory) = &config.directory else {
+            return vec![];
+        };
+        let mut paths = vec![];
+        walk_directory(Path::new(directory), &config.extensions, &mut paths);
+        paths.sort();
+
+        let files = paths
+            .iter()
+            .filter_map(|path| {
+                let text = std::fs::read_to_string(path).ok()?;
+                let name = path.strip_prefix(directory).unwrap_or(path);
+                Some(CodeFile {
+                    name: name.display().to_string(),
+                    text,
+                })
+            })
+            .filter(|file| !file.text.trim().is_empty())
+            .take(config.max_files)
+            .collect::<Vec<_>>();
+        if files.is_empty() {
+            warn!("No source files found in {directory}");
+        }
+        files
+    }
+
+    /// The file system is not available on web, so only the bundled files are typed.
+    #[cfg(feature = "web")]
+    fn load_directory(_config: &CodeTyperConfig) -> Vec<CodeFile> {
+        vec![]
+    }
+}
+
+/// Collects the paths of all files with one of the given extensions under `dir`.
+#[cfg(not(feature = "web"))]
+fn walk_directory(
+    dir: &std::path::Path,
+    extensions: &[String],
+    paths: &mut Vec<std::path::PathBuf>,
+) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            walk_directory(&path, extensions, paths);
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.iter().any(|x| x == ext))
+        {
+            paths.push(path);
+        }
+    }
+}
+
+fn load_code_sources(mut commands: Commands, config: Res<Config>) {
+    let files = CodeSources::load(&config.code_typer);
+    if files.is_empty() {
+        info!("Typing filler code");
+        return;
+    }
+
+    info!("Typing {} source files", files.len());
+    commands.insert_resource(CodeSources(files.into()));
+}
+
+/// An infinite iterator over the intro text followed by the source files in a loop, with the
+/// generated code typed after each source file.
+#[derive(Clone)]
+pub struct CodeGenerator {
+    intro: Chars<'static>,
+    files: Arc<[CodeFile]>,
+    /// The index of the file being typed, or `files.len()` for the generated code.
+    file: usize,
+    /// The index of the source file to type after the current file.
+    next_file: usize,
+    /// The byte offset of the next character in the file being typed.
+    pos: usize,
+    /// The generated code being typed.
+    generated: Arc<str>,
+    /// The latest generated code, which will be typed after the current source file.
+    next_generated: Arc<str>,
+}
 
 impl Default for CodeGenerator {
     fn default() -> Self {
-        Self(FILLER_CODE.chars().cycle())
+        Self::new(&CodeSources::default())
+    }
+}
+
+impl Iterator for CodeGenerator {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if let Some(c) = self.intro.next() {
+            return Some(c);
+        }
+
+        loop {
+            if let Some(c) = self.text()[self.pos..].chars().next() {
+                self.pos += c.len_utf8();
+                return Some(c);
+            }
+            self.pos = 0;
+            let typed_source = self.file < self.files.len();
+            if (typed_source && !self.next_generated.is_empty()) || self.files.is_empty() {
+                self.file = self.files.len();
+                self.generated = self.next_generated.clone();
+            } else {
+                self.file = self.next_file;
+                self.next_file = (self.file + 1) % self.files.len();
+            }
+        }
+    }
+}
+
+impl CodeGenerator {
+    pub fn new(sources: &CodeSources) -> Self {
+        Self {
+            intro: INTRO_CODE.chars(),
+            files: sources.0.clone(),
+            file: 0,
+            next_file: 1 % sources.0.len().max(1),
+            pos: 0,
+            generated: "".into(),
+            next_generated: "".into(),
+        }
+    }
+
+    /// Creates the generator for the code tab with the given index. Only the first tab types the
+    /// intro, and each tab starts from a different source file.
+    pub fn for_tab(sources: &CodeSources, tab: usize) -> Self {
+        let mut generator = Self::new(sources);
+        if tab > 0 {
+            generator.intro = "".chars();
+            generator.file = tab % generator.files.len();
+            generator.next_file = (generator.file + 1) % generator.files.len();
+        }
+        generator
+    }
+
+    /// The name of the file being typed.
+    pub fn file_name(&self) -> &str {
+        match self.files.get(self.file) {
+            Some(file) => &file.name,
+            None => GENERATED_FILE_NAME,
+        }
+    }
+
+    /// The text of the file being typed.
+    fn text(&self) -> &str {
+        match self.files.get(self.file) {
+            Some(file) => &file.text,
+            None => &self.generated,
+        }
+    }
+
+    /// Sets the code to type after the current source file.
+    pub fn set_generated(&mut self, code: Arc<str>) {
+        self.next_generated = code;
+    }
+
+    /// Returns the rest of the line from the next visible character, up to the given number of
+    /// characters. Like `next_visible`, this skips whitespace and line breaks first.
+    pub fn preview(&self, max_chars: usize) -> String {
+        self.clone()
+            .skip_while(|c| c.is_whitespace())
+            .take_while(|&c| c != '\n')
+            .take(max_chars)
+            .collect()
+    }
+
+    /// Returns the next character that isn't whitespace.
+    pub fn next_visible(&self) -> char {
+        self.clone().find(|c| !c.is_whitespace()).unwrap()
     }
 }
 
 #[derive(Component, Reflect)]
 pub struct CodeTyper {
-    /// Characters to type per key press.
-    pub chars_per_key: usize,
-    /// The number of lines currently displayed.
-    pub lines_count: usize,
-    /// The maximum number of lines to display before old lines start getting deleted.
+    /// The index of the code tab this typer belongs to.
+    pub tab: usize,
+    /// The number of lines typed, including lines that were deleted from the history.
+    pub lines_typed: f64,
+    /// The maximum number of finished lines to keep before old lines start getting deleted.
     pub lines_max: usize,
+    /// Finished lines that have not been moved into the code history yet, oldest first.
+    #[reflect(ignore)]
+    pub finished_lines: VecDeque<Vec<TextSection>>,
     /// An infinite iterator that yields the next character that will be added.
     #[reflect(ignore)]
     pub code: CodeGenerator,
+    /// Colors the characters as they are added.
+    pub syntax: SyntaxHighlighter,
+    /// Mistyped characters that must be deleted with Backspace (accuracy mode).
+    pub typos: String,
+    /// The number of correct keys typed in a row (accuracy mode).
+    pub combo: usize,
+    /// The number of sections at the end of the text that show typos and the ghost preview.
+    pub overlay_sections: usize,
 }
 
 impl Default for CodeTyper {
     fn default() -> Self {
         Self {
-            chars_per_key: 1,
-            lines_count: 1,
+            tab: 0,
+            lines_typed: 0.0,
             lines_max: 1,
+            finished_lines: VecDeque::new(),
             code: default(),
+            syntax: default(),
+            typos: String::new(),
+            combo: 0,
+            overlay_sections: 0,
         }
     }
 }
 
 impl CodeTyper {
-    pub fn enter(&mut self, text: &mut String, count: usize) -> f64 {
+    pub fn enter(&mut self, sections: &mut Vec<TextSection>, count: usize) -> f64 {
+        self.hide_overlay(sections);
+
         let mut typed_lines: f64 = 0.0;
         for _ in 0..count {
             loop {
                 // Push a character
-                let c = self.code.0.next().unwrap();
-                text.push(c);
+                let c = self.code.next().unwrap();
+                self.syntax.push(sections, c);
 
-                // If it was a newline, update typer's lines
+                // If it was a newline, move the line into the finished lines
                 if c == '\n' {
                     typed_lines += 1.0;
-                    self.lines_count += 1;
-                    if self.lines_count > self.lines_max {
-                        self.lines_count -= 1;
-                        // Remove the first line
-                        *text = text.split_off(text.find('\n').unwrap() + 1)
-                    }
+                    self.lines_typed += 1.0;
+                    self.finish_line(sections);
                 } else if !c.is_whitespace() {
                     // Stop when a visible character is reached
                     break;
@@ -82,27 +412,220 @@ impl CodeTyper {
         }
         typed_lines
     }
+
+    /// Moves the current line out of the text, leaving an empty section with the same style.
+    fn finish_line(&mut self, sections: &mut Vec<TextSection>) {
+        let style = sections.last().unwrap().style.clone();
+        let mut line = std::mem::replace(sections, vec![TextSection::new("", style.clone())]);
+        if let Some(last) = line.last_mut() {
+            last.value.pop();
+        }
+        line.retain(|section| !section.value.is_empty());
+        if line.is_empty() {
+            // Keep the height of empty lines
+            line.push(TextSection::new(" ", style));
+        }
+
+        self.finished_lines.push_back(line);
+        if self.finished_lines.len() > self.lines_max {
+            self.finished_lines.pop_front();
+        }
+    }
+
+    /// Removes the typos and ghost preview from the end of the text.
+    fn hide_overlay(&mut self, sections: &mut Vec<TextSection>) {
+        sections.truncate(sections.len().saturating_sub(self.overlay_sections));
+        self.overlay_sections = 0;
+    }
+
+    /// Shows the typos followed by a ghost preview of the upcoming code at the end of the text.
+    fn show_overlay(&mut self, sections: &mut Vec<TextSection>, preview_chars: usize) {
+        self.hide_overlay(sections);
+
+        let Some(style) = sections.last().map(|section| section.style.clone()) else {
+            return;
+        };
+        let colors = &self.syntax.colors;
+        if !self.typos.is_empty() {
+            sections.push(TextSection::new(
+                self.typos.clone(),
+                TextStyle {
+                    color: colors.typo,
+                    ..style.clone()
+                },
+            ));
+        }
+        sections.push(TextSection::new(
+            self.code.preview(preview_chars),
+            TextStyle {
+                color: colors.ghost,
+                ..style
+            },
+        ));
+        self.overlay_sections = if self.typos.is_empty() { 1 } else { 2 };
+    }
+
+    /// The multiplier on lines typed, from the combo.
+    pub fn combo_multiplier(&self, config: &CodeTyperConfig) -> f64 {
+        (1.0 + self.combo as f64 * config.combo_bonus).min(config.combo_max)
+    }
+}
+
+/// Types from the loaded source files in newly spawned code typers.
+fn apply_code_sources(
+    sources: Res<CodeSources>,
+    mut typer_query: Query<&mut CodeTyper, Added<CodeTyper>>,
+) {
+    for mut typer in &mut typer_query {
+        typer.code = CodeGenerator::for_tab(&sources, typer.tab);
+    }
 }
 
 pub fn type_code(
     mut char_events: EventReader<ReceivedCharacter>,
     keyboard_input: Res<Input<ScanCode>>,
+    game_time: Res<GameTime>,
+    real_time: Res<Time<Real>>,
+    config: Res<Config>,
+    accuracy: Res<AccuracyMode>,
+    tabs: Res<CodeTabs>,
+    passive: Res<PassiveCodeTyper>,
+    audio: Res<Audio>,
+    audio_assets: Res<AudioAssets>,
+    mut errors: ResMut<CompilerErrors>,
+    mut simulation: ResMut<Simulation>,
+    mut speed: ResMut<TypingSpeed>,
     mut typer_query: Query<(&mut CodeTyper, &mut Text)>,
     mut events: EventWriter<LinesAddedEvent>,
 ) {
-    let keys = char_events
+    let chars = char_events
         .read()
-        // Filter out Escape, Backspace, and Delete
-        .filter(|ev| !"\u{1b}\u{8}\u{7f}".contains(ev.char))
-        .count()
-        .min(keyboard_input.get_just_pressed().count());
-    if keys == 0 {
+        // Filter out Escape and Delete
+        .filter(|ev| !"\u{1b}\u{7f}".contains(ev.char))
+        .map(|ev| ev.char)
+        .take(keyboard_input.get_just_pressed().count())
+        .collect::<Vec<_>>();
+    if chars.is_empty() || game_time.is_paused() {
         return;
     }
 
+    // Keys go towards resolving the compiler error until it's resolved
+    if let Some(error) = &mut errors.active {
+        let resolved = chars.iter().any(|&c| {
+            if c == '\u{8}' {
+                audio.play(audio_assets.get_sfx(SoundEffectKind::Backspace));
+            }
+            error.press(c)
+        });
+        if resolved {
+            errors.active = None;
+        }
+        return;
+    }
+    // Only type into the focused tab
+    let typer_query = typer_query
+        .iter_mut()
+        .filter(|(typer, _)| typer.tab == tabs.focused);
+
+    if !accuracy.0 {
+        let keys = chars.iter().filter(|&&c| c != '\u{8}').count();
+        if keys == 0 {
+            return;
+        }
+        speed.record_keys(real_time.elapsed_seconds_f64(), keys);
+        let bonus = typing_speed_bonus(&simulation, &speed);
+
+        let chars = keys.saturating_mul(simulation.chars_per_key) as f64;
+        let (count, overflow_lines) = passive.split_overflow(chars);
+        for (mut typer, mut text) in typer_query {
+            let lines = typer.enter(&mut text.sections, count) + overflow_lines;
+            events.send(LinesAddedEvent {
+                count: lines * bonus,
+                source: IncomeSource::Typing,
+            });
+        }
+        return;
+    }
+
+    let config = &config.code_typer;
+    let mut correct_keys = 0;
+    for (mut typer, mut text) in typer_query {
+        let mut lines = 0.0;
+        for &c in &chars {
+            if c == '\u{8}' {
+                typer.typos.pop();
+                continue;
+            }
+            // Whitespace is typed automatically
+            if c.is_whitespace() || c.is_control() {
+                continue;
+            }
+
+            if typer.typos.is_empty() && c == typer.code.next_visible() {
+                correct_keys += 1;
+                typer.combo += 1;
+                let (count, overflow_lines) =
+                    passive.split_overflow(simulation.chars_per_key as f64);
+                lines += (typer.enter(&mut text.sections, count) + overflow_lines)
+                    * typer.combo_multiplier(config);
+            } else {
+                typer.typos.push(c);
+                typer.combo = 0;
+                simulation.tech_debt += config.typo_tech_debt;
+            }
+        }
+        typer.show_overlay(&mut text.sections, config.preview_chars);
+
+        events.send(LinesAddedEvent {
+            count: lines * typing_speed_bonus(&simulation, &speed),
+            source: IncomeSource::Typing,
+        });
+    }
+    speed.record_keys(real_time.elapsed_seconds_f64(), correct_keys);
+}
+
+fn typing_speed_bonus(simulation: &Simulation, speed: &TypingSpeed) -> f64 {
+    if simulation.typing_speed_bonus {
+        speed.bonus()
+    } else {
+        1.0
+    }
+}
+
+/// Shows or hides the typos and ghost preview after the code has changed.
+fn update_typing_overlay(
+    config: Res<Config>,
+    accuracy: Res<AccuracyMode>,
+    mut typer_query: Query<(&mut CodeTyper, &mut Text)>,
+) {
     for (mut typer, mut text) in &mut typer_query {
-        let count = keys * typer.chars_per_key;
-        let lines = typer.enter(&mut text.sections[0].value, count);
-        events.send(LinesAddedEvent { count: lines });
+        if accuracy.0 {
+            if typer.overlay_sections == 0 {
+                typer.show_overlay(&mut text.sections, config.code_typer.preview_chars);
+            }
+        } else if typer.overlay_sections > 0 {
+            typer.typos.clear();
+            typer.combo = 0;
+            typer.hide_overlay(&mut text.sections);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_code_is_typed_after_each_file() {
+        let files = ["a", "b"].map(|text| CodeFile {
+            name: format!("{text}.rs"),
+            text: text.to_string(),
+        });
+        let mut generator = CodeGenerator::new(&CodeSources(files.into()));
+        generator.intro = "".chars();
+        assert_eq!(generator.by_ref().take(2).collect::<String>(), "ab");
+
+        generator.set_generated("g".into());
+        assert_eq!(generator.take(6).collect::<String>(), "gagbga");
     }
 }
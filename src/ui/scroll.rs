@@ -1,6 +1,7 @@
 use bevy::input::mouse::MouseScrollUnit;
 use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 use crate::AppSet;
 
@@ -22,9 +23,10 @@ pub struct ScrollContent {
 
 fn mouse_scroll(
     mut events: EventReader<MouseWheel>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
     mut scroll_query: Query<(&mut ScrollContent, &mut Style, &Parent, &Node)>,
     mut scrollbar_query: Query<&mut Style, Without<ScrollContent>>,
-    node_query: Query<&Node>,
+    node_query: Query<(&Node, &GlobalTransform)>,
 ) {
     let pixels = events
         .read()
@@ -37,13 +39,24 @@ fn mouse_scroll(
         })
         .sum::<f32>();
 
+    let cursor = window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
     for (mut scroll, mut style, parent, node) in &mut scroll_query {
-        scroll.position += pixels * scroll.sensitivity;
+        let (parent_node, parent_gt) = node_query.get(parent.get()).unwrap();
+        // Only scroll the content under the cursor
+        let hovered =
+            cursor.is_none_or(|cursor| parent_node.logical_rect(parent_gt).contains(cursor));
+        if hovered {
+            scroll.position += pixels * scroll.sensitivity;
+        }
 
         let height = node.size().y;
-        let parent_height = node_query.get(parent.get()).unwrap().size().y;
+        let parent_height = parent_node.size().y;
         if parent_height <= 0.0 {
-            return;
+            continue;
         }
         let overflow = (height - parent_height).max(0.0);
         scroll.position = scroll.position.clamp(-overflow, 0.0);
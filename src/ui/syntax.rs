@@ -0,0 +1,344 @@
+use bevy::prelude::*;
+
+const KEYWORDS: [&str; 39] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "yield",
+];
+
+/// The color of each kind of token.
+#[derive(Reflect, Default, Clone)]
+pub struct SyntaxColors {
+    pub plain: Color,
+    pub keyword: Color,
+    pub string: Color,
+    pub comment: Color,
+    pub number: Color,
+    pub macro_name: Color,
+    pub lifetime: Color,
+    /// Upcoming code in accuracy mode.
+    pub ghost: Color,
+    /// Mistyped characters in accuracy mode.
+    pub typo: Color,
+}
+
+#[derive(Reflect, Default, Copy, Clone, PartialEq, Eq, Debug)]
+enum LexState {
+    #[default]
+    Normal,
+    Word,
+    Number,
+    /// A slash that may start a comment.
+    Slash,
+    LineComment,
+    BlockComment {
+        star: bool,
+    },
+    String {
+        escaped: bool,
+    },
+    CharLiteral {
+        escaped: bool,
+    },
+    /// A quote followed by `len` identifier characters, which could still be a char literal.
+    Lifetime {
+        len: usize,
+    },
+}
+
+/// A streaming Rust tokenizer that colors text one character at a time.
+#[derive(Reflect, Default, Clone)]
+pub struct SyntaxHighlighter {
+    pub colors: SyntaxColors,
+    state: LexState,
+    /// The word being typed, to recognize keywords and macros once it ends.
+    word: String,
+}
+
+impl SyntaxHighlighter {
+    pub fn new(colors: SyntaxColors) -> Self {
+        Self {
+            colors,
+            ..default()
+        }
+    }
+
+    /// Continues the line comment that the text starts with.
+    pub fn in_line_comment(mut self) -> Self {
+        self.state = LexState::LineComment;
+        self
+    }
+
+    /// Appends a character to the text sections, colored by the token it belongs to.
+    pub fn push(&mut self, sections: &mut Vec<TextSection>, c: char) {
+        let colors = &self.colors;
+
+        // Continue or end the current token
+        match self.state {
+            LexState::Normal => {},
+            LexState::Word => {
+                if is_ident(c) {
+                    self.word.push(c);
+                    return append(sections, c, colors.plain);
+                }
+
+                let color = if c == '!' {
+                    colors.macro_name
+                } else if KEYWORDS.contains(&self.word.as_str()) {
+                    colors.keyword
+                } else {
+                    colors.plain
+                };
+                recolor_last(sections, color);
+                self.word.clear();
+                self.state = LexState::Normal;
+                if c == '!' {
+                    return append(sections, c, colors.macro_name);
+                }
+            },
+            LexState::Number => {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    return append(sections, c, colors.number);
+                }
+                self.state = LexState::Normal;
+            },
+            LexState::Slash => {
+                self.state = match c {
+                    '/' => LexState::LineComment,
+                    '*' => LexState::BlockComment { star: false },
+                    _ => LexState::Normal,
+                };
+                if self.state != LexState::Normal {
+                    recolor_last(sections, colors.comment);
+                    return append(sections, c, colors.comment);
+                }
+            },
+            LexState::LineComment => {
+                if c == '\n' {
+                    self.state = LexState::Normal;
+                }
+                return append(sections, c, colors.comment);
+            },
+            LexState::BlockComment { star } => {
+                self.state = if star && c == '/' {
+                    LexState::Normal
+                } else {
+                    LexState::BlockComment { star: c == '*' }
+                };
+                return append(sections, c, colors.comment);
+            },
+            LexState::String { escaped } => {
+                self.state = match c {
+                    _ if escaped => LexState::String { escaped: false },
+                    '\\' => LexState::String { escaped: true },
+                    '"' => LexState::Normal,
+                    _ => LexState::String { escaped: false },
+                };
+                return append(sections, c, colors.string);
+            },
+            LexState::CharLiteral { escaped } => {
+                self.state = match c {
+                    _ if escaped => LexState::CharLiteral { escaped: false },
+                    '\\' => LexState::CharLiteral { escaped: true },
+                    '\'' | '\n' => LexState::Normal,
+                    _ => LexState::CharLiteral { escaped: false },
+                };
+                return append(sections, c, colors.string);
+            },
+            LexState::Lifetime { len } => {
+                // Lifetimes can only start with an identifier character, so this is a char
+                // literal, like '"' or '\n'
+                if len == 0 && !is_ident(c) && c != '\n' {
+                    self.state = LexState::CharLiteral { escaped: c == '\\' };
+                    recolor_last(sections, colors.string);
+                    return append(sections, c, colors.string);
+                }
+                if is_ident(c) {
+                    self.state = LexState::Lifetime { len: len + 1 };
+                    return append(sections, c, colors.lifetime);
+                }
+                self.state = LexState::Normal;
+                // It was a char literal after all, like 'a'
+                if c == '\'' && len == 1 {
+                    recolor_last(sections, colors.string);
+                    return append(sections, c, colors.string);
+                }
+            },
+        }
+
+        // Start a new token
+        match c {
+            _ if c.is_alphabetic() || c == '_' => {
+                self.state = LexState::Word;
+                self.word.push(c);
+                // Start a new section so that the word can be recolored once it ends
+                push_section(sections, c, colors.plain);
+            },
+            _ if c.is_ascii_digit() => {
+                self.state = LexState::Number;
+                append(sections, c, colors.number);
+            },
+            '/' => {
+                self.state = LexState::Slash;
+                push_section(sections, c, colors.plain);
+            },
+            '"' => {
+                self.state = LexState::String { escaped: false };
+                append(sections, c, colors.string);
+            },
+            '\'' => {
+                self.state = LexState::Lifetime { len: 0 };
+                push_section(sections, c, colors.lifetime);
+            },
+            _ => append(sections, c, colors.plain),
+        }
+    }
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Appends a character to the last section, or to a new section if the color is different.
+fn append(sections: &mut Vec<TextSection>, c: char, color: Color) {
+    match sections.last_mut() {
+        Some(section) if section.style.color == color => section.value.push(c),
+        _ => push_section(sections, c, color),
+    }
+}
+
+/// Appends a character in a new section with the same style as the last section.
+fn push_section(sections: &mut Vec<TextSection>, c: char, color: Color) {
+    let mut style = sections
+        .last()
+        .map(|section| section.style.clone())
+        .unwrap_or_default();
+    style.color = color;
+    sections.push(TextSection::new(c, style));
+}
+
+/// Recolors the last section, merging it into the previous section if they now match.
+fn recolor_last(sections: &mut Vec<TextSection>, color: Color) {
+    let Some(last) = sections.last_mut() else {
+        return;
+    };
+    last.style.color = color;
+
+    if let [.., prev, last] = sections.as_mut_slice() {
+        if prev.style.color == last.style.color {
+            let value = std::mem::take(&mut last.value);
+            prev.value.push_str(&value);
+            sections.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors() -> SyntaxColors {
+        SyntaxColors {
+            plain: Color::WHITE,
+            keyword: Color::PURPLE,
+            string: Color::GREEN,
+            comment: Color::GRAY,
+            number: Color::ORANGE,
+            macro_name: Color::BLUE,
+            lifetime: Color::YELLOW,
+            ghost: Color::DARK_GRAY,
+            typo: Color::RED,
+        }
+    }
+
+    /// Highlights the text and returns each run of same-colored characters.
+    fn highlight(text: &str) -> Vec<(String, Color)> {
+        let mut highlighter = SyntaxHighlighter::new(colors());
+        let mut sections = vec![];
+        for c in text.chars() {
+            highlighter.push(&mut sections, c);
+        }
+
+        let mut runs: Vec<(String, Color)> = vec![];
+        for section in sections {
+            match runs.last_mut() {
+                Some((value, color)) if *color == section.style.color => {
+                    value.push_str(&section.value)
+                },
+                _ => runs.push((section.value, section.style.color)),
+            }
+        }
+        runs
+    }
+
+    fn run(value: &str, color: Color) -> (String, Color) {
+        (value.to_string(), color)
+    }
+
+    #[test]
+    fn keywords() {
+        let c = colors();
+        assert_eq!(
+            highlight("pub fn main() "),
+            [
+                run("pub", c.keyword),
+                run(" ", c.plain),
+                run("fn", c.keyword),
+                run(" main() ", c.plain),
+            ],
+        );
+    }
+
+    #[test]
+    fn macros() {
+        let c = colors();
+        assert_eq!(
+            highlight("println!(\"{x}\");"),
+            [
+                run("println!", c.macro_name),
+                run("(", c.plain),
+                run("\"{x}\"", c.string),
+                run(");", c.plain),
+            ],
+        );
+    }
+
+    #[test]
+    fn lifetimes() {
+        let c = colors();
+        assert_eq!(
+            highlight("&'a str"),
+            [
+                run("&", c.plain),
+                run("'a", c.lifetime),
+                run(" str", c.plain)
+            ],
+        );
+    }
+
+    #[test]
+    fn char_literals() {
+        let c = colors();
+        for literal in ["'a'", "'\"'", "'\\n'", "'\\''", "' '"] {
+            assert_eq!(
+                highlight(&format!("{literal};")),
+                [run(literal, c.string), run(";", c.plain)],
+                "{literal}",
+            );
+        }
+    }
+
+    #[test]
+    fn block_comments_across_lines() {
+        let c = colors();
+        assert_eq!(
+            highlight("/* one\n * two */ 1"),
+            [
+                run("/* one\n * two */", c.comment),
+                run(" ", c.plain),
+                run("1", c.number),
+            ],
+        );
+    }
+}
@@ -7,6 +7,7 @@ use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use rand::Rng;
 use strum::EnumCount;
 use strum::EnumIter;
 use strum::IntoEnumIterator;
@@ -19,19 +20,24 @@ use crate::config::Config;
 use crate::physics::PhysicsSettings;
 use crate::physics::UNIT_SPEED;
 use crate::simulation::AtlasList;
+use crate::simulation::DailyChallenge;
+use crate::simulation::GameMode;
+use crate::simulation::IncomeSource;
+use crate::simulation::JamTheme;
 use crate::simulation::LinesAddedEvent;
 use crate::simulation::PassiveCodeTyper;
 use crate::simulation::PassiveEntitySpawner;
+use crate::simulation::RunRng;
 use crate::simulation::Simulation;
 use crate::simulation::SpawnEvent;
-use crate::simulation::SpritePack;
 use crate::simulation::SpritePackEvent;
+use crate::simulation::BASE_WPM;
 use crate::state::editor_screen::spawn_editor_screen;
+use crate::state::editor_screen::JamClock;
 use crate::state::editor_screen::SceneView;
 use crate::state::editor_screen::SceneViewBounds;
 use crate::state::editor_screen::UpgradeOutline;
 use crate::state::AppState;
-use crate::ui::CodeTyper;
 use crate::util::pretty_num;
 use crate::AppRoot;
 use crate::AppSet;
@@ -75,6 +81,8 @@ pub struct Upgrade {
     pub no_outline: bool,
     /// If true, this upgrade's count will not be included in the outline.
     pub no_count: bool,
+    /// The code file that this upgrade opens as a new tab in the code panel.
+    pub code_file: Option<String>,
     /// The amount of technical debt this upgrade adds when you install it.
     pub tech_debt: f64,
     /// How much this upgrade contributes to the Presentation score of your submission.
@@ -90,6 +98,8 @@ pub struct Upgrade {
     pub weight: f32,
     /// How many more copies of this upgrade can be installed.
     pub remaining: usize,
+    /// If true, more copies of this upgrade can be installed after `remaining` reaches 0.
+    pub uncapped: bool,
     /// The minimum number of entities required for this upgrade to be offered.
     pub entity_min: f64,
     /// The maximum number of entities allowed for this upgrade to be offered.
@@ -128,6 +138,7 @@ impl Default for Upgrade {
             sound: Some(SoundEffectKind::DefaultUpgrade),
             no_outline: false,
             no_count: false,
+            code_file: None,
             tech_debt: 0.0,
             presentation_score: 0.0,
             fun_score: 0.0,
@@ -136,6 +147,7 @@ impl Default for Upgrade {
 
             weight: 0.0,
             remaining: 1,
+            uncapped: false,
             entity_min: 0.0,
             entity_max: f64::INFINITY,
             line_min: 0.0,
@@ -155,8 +167,14 @@ impl Default for Upgrade {
 }
 
 impl Upgrade {
-    pub fn is_unlocked(&self, simulation: &Simulation, outline: &UpgradeOutline) -> bool {
-        self.remaining > 0
+    pub fn is_unlocked(
+        &self,
+        kind: UpgradeKind,
+        simulation: &Simulation,
+        outline: &UpgradeOutline,
+    ) -> bool {
+        (self.remaining > 0 || self.uncapped)
+            && simulation.allows_upgrade(kind)
             && (self.entity_min <= simulation.entities && simulation.entities <= self.entity_max)
             && (self.line_min <= simulation.lines && simulation.lines <= self.line_max)
             && (self.upgrade_min <= simulation.upgrades && simulation.upgrades <= self.upgrade_max)
@@ -176,7 +194,7 @@ impl Upgrade {
         (self.base_cost
             * self
                 .cost_scale_factor
-                .powf(simulation.tech_debt * simulation.tech_debt_multiplier))
+                .powf(simulation.effective_tech_debt()))
         .floor()
     }
 
@@ -194,7 +212,7 @@ fn process_new_installed_upgrades(
 ) {
     for event in events.read() {
         let upgrade = &mut upgrade_list[event.kind];
-        upgrade.remaining -= 1;
+        upgrade.remaining = upgrade.remaining.saturating_sub(1);
         simulation.tech_debt += upgrade.tech_debt;
         simulation.presentation_score += upgrade.presentation_score;
         simulation.fun_score += upgrade.fun_score;
@@ -277,13 +295,14 @@ impl UpgradeSequence {
         upgrade_list: &UpgradeList,
         simulation: &Simulation,
         outline: &UpgradeOutline,
+        mut rng: impl Rng,
     ) -> (Vec<UpgradeKind>, String) {
         // Check the stack of upgrades first
         while let Some((upgrades, desc)) = self.stack.pop() {
             let upgrades = upgrades
                 .iter()
                 .copied()
-                .filter(|&kind| upgrade_list[kind].is_unlocked(simulation, outline))
+                .filter(|&kind| upgrade_list[kind].is_unlocked(kind, simulation, outline))
                 .collect::<Vec<_>>();
 
             if !upgrades.is_empty() {
@@ -298,12 +317,10 @@ impl UpgradeSequence {
                 let upgrade = &upgrade_list[kind];
                 // This prevents the tutorial upgrades from being offered when
                 // all other upgrades are exhausted.
-                upgrade.weight > 0.0 && upgrade.is_unlocked(simulation, outline)
+                upgrade.weight > 0.0 && upgrade.is_unlocked(kind, simulation, outline)
             })
             .collect::<Vec<_>>()
-            .choose_multiple_weighted(&mut thread_rng(), self.slots, |&kind| {
-                upgrade_list[kind].weight
-            })
+            .choose_multiple_weighted(&mut rng, self.slots, |&kind| upgrade_list[kind].weight)
             .unwrap()
             .copied()
             .collect::<Vec<_>>();
@@ -319,9 +336,16 @@ impl UpgradeSequence {
 }
 
 /// Loads the sequence of upgrades offered.
-fn load_upgrade_sequence(mut commands: Commands) {
+fn load_upgrade_sequence(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    theme: Res<JamTheme>,
+    challenge: Res<DailyChallenge>,
+) {
     use UpgradeKind::*;
 
+    let theme = mode.theme(*theme, &challenge);
+
     commands.insert_resource(UpgradeSequence::new(vec![
         // Intro
         (
@@ -349,10 +373,7 @@ fn load_upgrade_sequence(mut commands: Commands) {
             vec![VelocityPlugin],
             "\"I should make the game interesting for a higher Fun score.\"".to_string(),
         ),
-        (
-            vec![Inspiration],
-            "\"I should spawn a lot of entities for a higher Theme Interpretation score... Is 50 enough?\"".to_string(),
-        ),
+        (vec![Inspiration], theme.tutorial_text().to_string()),
 
         // Reward
         (
@@ -419,10 +440,30 @@ macro_rules! generate_upgrade_list {
             ]);
 
             $world.insert_resource(upgrade_list);
+            uncap_repeatable_upgrades($world);
         }
     };
 }
 
+/// Lifts the limit on repeatable upgrades in sandbox mode, and removes the deadline upgrades since
+/// there is no deadline.
+fn uncap_repeatable_upgrades(world: &mut World) {
+    if *world.resource::<GameMode>() != GameMode::Sandbox {
+        return;
+    }
+
+    let mut upgrade_list = world.resource_mut::<UpgradeList>();
+    for kind in [UpgradeKind::Crunch, UpgradeKind::Extension] {
+        upgrade_list[kind].remaining = 0;
+    }
+    for upgrade in &mut upgrade_list.0 {
+        // Upgrades with a single copy are one-time unlocks, not repeatable
+        if upgrade.remaining > 1 {
+            upgrade.uncapped = true;
+        }
+    }
+}
+
 generate_upgrade_list!(
     |world|
 
@@ -468,9 +509,11 @@ generate_upgrade_list!(
                 count: simulation.entities * 3.0,
                 position: (bounds.min.xy() + bounds.max.xy()) / 2.0,
                 custom_cap: None,
+                source: IncomeSource::Upgrade(UtilPlugin),
             });
             line_events.send(LinesAddedEvent {
                 count: simulation.lines * 3.0,
+                source: IncomeSource::Upgrade(UtilPlugin),
             });
 
         })),
@@ -505,9 +548,12 @@ generate_upgrade_list!(
             mut events: EventWriter<SpritePackEvent>,
             mut simulation: ResMut<Simulation>,
             atlas_list: Res<AtlasList>,
+            mut rng: ResMut<RunRng>,
         | {
-            simulation.skin_set.replace_sprite_pack(&atlas_list, SpritePack::OneBit, &mut thread_rng());
-            events.send(SpritePackEvent);
+            if let Some(sprite_pack) = atlas_list.sprite_pack("1-bit") {
+                simulation.replace_sprite_pack(&atlas_list, sprite_pack, &mut rng.skins);
+                events.send(SpritePackEvent);
+            }
         })),
         ..default()
     },
@@ -520,9 +566,12 @@ generate_upgrade_list!(
             mut events: EventWriter<SpritePackEvent>,
             mut simulation: ResMut<Simulation>,
             atlas_list: Res<AtlasList>,
+            mut rng: ResMut<RunRng>,
         | {
-            simulation.skin_set.replace_sprite_pack(&atlas_list, SpritePack::Rpg, &mut thread_rng());
-            events.send(SpritePackEvent);
+            if let Some(sprite_pack) = atlas_list.sprite_pack("rpg") {
+                simulation.replace_sprite_pack(&atlas_list, sprite_pack, &mut rng.skins);
+                events.send(SpritePackEvent);
+            }
         })),
         ..default()
     },
@@ -535,9 +584,12 @@ generate_upgrade_list!(
             mut events: EventWriter<SpritePackEvent>,
             mut simulation: ResMut<Simulation>,
             atlas_list: Res<AtlasList>,
+            mut rng: ResMut<RunRng>,
         | {
-            simulation.skin_set.replace_sprite_pack(&atlas_list, SpritePack::Ninja, &mut thread_rng());
-            events.send(SpritePackEvent);
+            if let Some(sprite_pack) = atlas_list.sprite_pack("ninja") {
+                simulation.replace_sprite_pack(&atlas_list, sprite_pack, &mut rng.skins);
+                events.send(SpritePackEvent);
+            }
         })),
         ..default()
     },
@@ -556,9 +608,91 @@ generate_upgrade_list!(
         ..default()
     },
 
+    ColorTheory: Upgrade {
+        name: "Color Theory".to_string(),
+        desc: "Offers a choice between different color palettes for your entities.".to_string(),
+        no_outline: true,
+        base_cost: 50.0,
+        weight: 1.0,
+        entity_min: 50.0,
+        install: Some(world.register_system(|mut sequence: ResMut<UpgradeSequence>| {
+            sequence.push(
+                vec![PalettePastel, PaletteNeon, PaletteGrayscale, PaletteTeam],
+                "You can only select one option. \
+                 The rejected options will never appear again.".to_string(),
+            );
+        })),
+        ..default()
+    },
+
+    PalettePastel: Upgrade {
+        name: "Color Palette (Pastel)".to_string(),
+        desc: "Recolors your entities with soft pastel colors. Makes your game prettier.".to_string(),
+        presentation_score: 5.0,
+        install: Some(world.register_system(|
+            mut events: EventWriter<SpritePackEvent>,
+            mut simulation: ResMut<Simulation>,
+            config: Res<Config>,
+            mut rng: ResMut<RunRng>,
+        | {
+            simulation.set_entity_colors(config.palette.colors("pastel"), &mut rng.skins);
+            events.send(SpritePackEvent);
+        })),
+        ..default()
+    },
+
+    PaletteNeon: Upgrade {
+        name: "Color Palette (Neon)".to_string(),
+        desc: "Recolors your entities with bright neon colors. Makes your game prettier.".to_string(),
+        presentation_score: 6.0,
+        install: Some(world.register_system(|
+            mut events: EventWriter<SpritePackEvent>,
+            mut simulation: ResMut<Simulation>,
+            config: Res<Config>,
+            mut rng: ResMut<RunRng>,
+        | {
+            simulation.set_entity_colors(config.palette.colors("neon"), &mut rng.skins);
+            events.send(SpritePackEvent);
+        })),
+        ..default()
+    },
+
+    PaletteGrayscale: Upgrade {
+        name: "Color Palette (Grayscale)".to_string(),
+        desc: "Recolors your entities in shades of gray. Very artistic. Makes your game prettier.".to_string(),
+        presentation_score: 4.0,
+        install: Some(world.register_system(|
+            mut events: EventWriter<SpritePackEvent>,
+            mut simulation: ResMut<Simulation>,
+            config: Res<Config>,
+            mut rng: ResMut<RunRng>,
+        | {
+            simulation.set_entity_colors(config.palette.colors("grayscale"), &mut rng.skins);
+            events.send(SpritePackEvent);
+        })),
+        ..default()
+    },
+
+    PaletteTeam: Upgrade {
+        name: "Color Palette (Team)".to_string(),
+        desc: "Recolors your entities with bold team colors. Makes your game prettier.".to_string(),
+        presentation_score: 5.0,
+        install: Some(world.register_system(|
+            mut events: EventWriter<SpritePackEvent>,
+            mut simulation: ResMut<Simulation>,
+            config: Res<Config>,
+            mut rng: ResMut<RunRng>,
+        | {
+            simulation.set_entity_colors(config.palette.colors("team"), &mut rng.skins);
+            events.send(SpritePackEvent);
+        })),
+        ..default()
+    },
+
     SkinPlugin: Upgrade {
         name: "SkinPlugin".to_string(),
         desc: "Introduces a new entity skin. Makes your game prettier.".to_string(),
+        code_file: Some("render.rs".to_string()),
         tech_debt: 1.0,
         presentation_score: 4.0,
         base_cost: 5.0,
@@ -568,8 +702,9 @@ generate_upgrade_list!(
         install: Some(world.register_system(|
             mut simulation: ResMut<Simulation>,
             atlas_list: Res<AtlasList>,
+            mut rng: ResMut<RunRng>,
         | {
-            simulation.skin_set.add_skin(&atlas_list, &mut thread_rng());
+            simulation.add_skin(&atlas_list, &mut rng.skins);
         })),
         ..default()
     },
@@ -588,11 +723,27 @@ generate_upgrade_list!(
         ..default()
     },
 
+    AnimationPlugin: Upgrade {
+        name: "AnimationPlugin".to_string(),
+        desc: "Animates the entity skins that have multiple frames. Makes your game prettier.".to_string(),
+        code_file: Some("animation.rs".to_string()),
+        tech_debt: 1.0,
+        presentation_score: 10.0,
+        base_cost: 500.0,
+        weight: 1.0,
+        installed_min: vec![(SpritePackNinja, 1)],
+        install: Some(world.register_system(|mut simulation: ResMut<Simulation>| {
+            simulation.animated_sprites = true;
+        })),
+        ..default()
+    },
+
     // Fun score
 
     VelocityPlugin: Upgrade {
         name: "VelocityPlugin".to_string(),
         desc: "Allows entities to move. Makes your game more fun.".to_string(),
+        code_file: Some("physics.rs".to_string()),
         tech_debt: 1.0,
         fun_score: 5.0,
         base_cost: 5.0,
@@ -624,6 +775,7 @@ generate_upgrade_list!(
     RepulsionPlugin: Upgrade {
         name: "RepulsionPlugin".to_string(),
         desc: "Repels entities away from the cursor. Makes your game more fun.".to_string(),
+        code_file: Some("input.rs".to_string()),
         tech_debt: 1.0,
         fun_score: 20.0,
         base_cost: 2_000.0,
@@ -668,6 +820,7 @@ generate_upgrade_list!(
                     position: (bounds.min.xy() + bounds.max.xy()) / 2.0,
                     count: this.value,
                     custom_cap: Some(80),
+                    source: IncomeSource::Upgrade(SplashOfLifePlugin),
                 });
             }),
         ),
@@ -703,6 +856,7 @@ generate_upgrade_list!(
                     position: (bounds.min.xy() + bounds.max.xy()) / 2.0,
                     count: simulation.lines,
                     custom_cap: Some(200),
+                    source: IncomeSource::Upgrade(NukeOfLifePlugin),
                 });
 
                 simulation.lines = 0.0;
@@ -731,6 +885,7 @@ generate_upgrade_list!(
                     position: (bounds.min.xy() + bounds.max.xy()) / 2.0,
                     count: 1.0,
                     custom_cap: None,
+                    source: IncomeSource::Upgrade(TouchOfLifePlugin),
                 });
 
                 for mut scene_view in &mut scene_view_query {
@@ -875,7 +1030,10 @@ generate_upgrade_list!(
             upgrade_list: Res<UpgradeList>,
         | {
             let this = &upgrade_list[ImportLibrary];
-            events.send(LinesAddedEvent { count: this.value });
+            events.send(LinesAddedEvent {
+                count: this.value,
+                source: IncomeSource::Upgrade(ImportLibrary),
+            });
         })),
         ..default()
     },
@@ -891,6 +1049,7 @@ generate_upgrade_list!(
             mut commands: Commands,
             root: Res<AppRoot>,
             config: Res<Config>,
+            mode: Res<GameMode>,
             #[cfg(not(feature = "web"))]
             music: Res<BackgroundMusic>,
             #[cfg(not(feature = "web"))]
@@ -902,6 +1061,7 @@ generate_upgrade_list!(
                 &config.editor_screen,
                 &config.editor_screen.dracula_theme,
                 false,
+                *mode,
             );
             commands.entity(editor_screen).set_parent(root.ui);
 
@@ -924,6 +1084,7 @@ generate_upgrade_list!(
             mut commands: Commands,
             root: Res<AppRoot>,
             config: Res<Config>,
+            mode: Res<GameMode>,
             #[cfg(not(feature = "web"))]
             music: Res<BackgroundMusic>,
             #[cfg(not(feature = "web"))]
@@ -935,6 +1096,7 @@ generate_upgrade_list!(
                 &config.editor_screen,
                 &config.editor_screen.bamboo_theme,
                 false,
+                *mode,
             );
             commands.entity(editor_screen).set_parent(root.ui);
 
@@ -957,6 +1119,7 @@ generate_upgrade_list!(
             mut commands: Commands,
             root: Res<AppRoot>,
             config: Res<Config>,
+            mode: Res<GameMode>,
             #[cfg(not(feature = "web"))]
             music: Res<BackgroundMusic>,
             #[cfg(not(feature = "web"))]
@@ -968,6 +1131,7 @@ generate_upgrade_list!(
                 &config.editor_screen,
                 &config.editor_screen.synthwave_theme,
                 false,
+                *mode,
             );
             commands.entity(editor_screen).set_parent(root.ui);
 
@@ -992,12 +1156,8 @@ generate_upgrade_list!(
         ".to_string(),
         sound: Some(SoundEffectKind::Keyboard),
         base_cost: 15.0,
-        install: Some(world.register_system(|
-            mut typer_query: Query<&mut CodeTyper>,
-        | {
-            for mut typer in &mut typer_query {
-                typer.chars_per_key += 5;
-            }
+        install: Some(world.register_system(|mut simulation: ResMut<Simulation>| {
+            simulation.chars_per_key = simulation.chars_per_key.saturating_add(5);
         })),
         ..default()
     },
@@ -1014,12 +1174,10 @@ generate_upgrade_list!(
         weight: 3.5,
         remaining: 2,
         install: Some(world.register_system(|
-            mut typer_query: Query<&mut CodeTyper>,
+            mut simulation: ResMut<Simulation>,
             mut upgrade_list: ResMut<UpgradeList>,
         | {
-            for mut typer in &mut typer_query {
-                typer.chars_per_key *= 2;
-            }
+            simulation.chars_per_key = simulation.chars_per_key.saturating_mul(2);
             // Update this upgrade for the next iteration: Ergonomic Keyboard.
             let this = &mut upgrade_list[MechanicalKeyboard];
             // Cost scaling of this is independent of tech debt.
@@ -1052,17 +1210,16 @@ generate_upgrade_list!(
         entity_min: 100.0,
         installed_min: vec![(MechanicalKeyboard, 1)],
         install: Some(world.register_system(|
-            mut typer_query: Query<&mut CodeTyper>,
+            mut simulation: ResMut<Simulation>,
             mut upgrade_list: ResMut<UpgradeList>,
         | {
             let this = &mut upgrade_list[TouchTyping];
             // Cost scaling of this is independent of tech debt.
             this.base_cost *= 2.0;
             this.value *= 2.0;
-            this.name = format!("Touch Typing ({} WPM)", 40 * 2i32.pow(3 - this.remaining as u32));
-            for mut typer in &mut typer_query {
-                typer.chars_per_key *= 2;
-            }
+            // The name keeps up when the upgrade is uncapped in the sandbox
+            this.name = format!("Touch Typing ({} WPM)", pretty_num(20.0 * this.value));
+            simulation.chars_per_key = simulation.chars_per_key.saturating_mul(2);
         })),
         ..default()
     },
@@ -1077,10 +1234,24 @@ generate_upgrade_list!(
         base_cost: 200_000.0,
         weight: 0.25,
         installed_min: vec![(TouchTyping, 4)],
-        install: Some(world.register_system(|mut typer_query: Query<&mut CodeTyper>| {
-            for mut typer in &mut typer_query {
-                typer.chars_per_key *= 2;
-            }
+        install: Some(world.register_system(|mut simulation: ResMut<Simulation>| {
+            simulation.chars_per_key = simulation.chars_per_key.saturating_mul(2);
+        })),
+        ..default()
+    },
+
+    FlowState: Upgrade {
+        name: "Flow State".to_string(),
+        desc: format!(
+            "You're in the zone. \
+            Multiplies the lines you type by your typing speed / {BASE_WPM} WPM.",
+        ),
+        sound: Some(SoundEffectKind::Keyboard),
+        base_cost: 400.0,
+        weight: 1.0,
+        installed_min: vec![(TouchTyping, 1)],
+        install: Some(world.register_system(|mut simulation: ResMut<Simulation>| {
+            simulation.typing_speed_bonus = true;
         })),
         ..default()
     },
@@ -1215,19 +1386,23 @@ generate_upgrade_list!(
 
     UnitTests: Upgrade {
         name: "Unit Tests".to_string(),
-        desc: "Improves the quality of the codebase. Reduces all future technical debt increases by 5%.".to_string(),
+        desc: "Improves the quality of the codebase. Reduces all future technical debt increases by 5% and compiler errors by 25%.".to_string(),
         tech_debt: -3.0,
         base_cost: 50.0,
         cost_scale_factor: 1.2,
         weight: 1.5,
         remaining: 2,
         tech_debt_min: 3.0,
-        install: Some(world.register_system(|mut upgrade_list: ResMut<UpgradeList>| {
+        install: Some(world.register_system(|
+            mut upgrade_list: ResMut<UpgradeList>,
+            mut simulation: ResMut<Simulation>,
+        | {
             for upgrade in &mut upgrade_list.0 {
                 if upgrade.tech_debt > 0.0 {
                     upgrade.tech_debt *= 0.95;
                 }
             }
+            simulation.compiler_error_multiplier *= 0.75;
         })),
         ..default()
     },
@@ -1253,18 +1428,22 @@ generate_upgrade_list!(
 
     ContinuousIntegration: Upgrade {
         name: "Continuous Integration".to_string(),
-        desc: "Reduces all future technical debt increases by 10%.".to_string(),
+        desc: "Reduces all future technical debt increases by 10% and compiler errors by 50%.".to_string(),
         tech_debt: 0.5,
         base_cost: 500.0,
         cost_scale_factor: 1.2,
         weight: 1.0,
         installed_min: vec![(Rtfm, 2), (UnitTests, 1)],
-        install: Some(world.register_system(|mut upgrade_list: ResMut<UpgradeList>| {
+        install: Some(world.register_system(|
+            mut upgrade_list: ResMut<UpgradeList>,
+            mut simulation: ResMut<Simulation>,
+        | {
             for upgrade in &mut upgrade_list.0 {
                 if upgrade.tech_debt > 0.0 {
                     upgrade.tech_debt *= 0.9;
                 }
             }
+            simulation.compiler_error_multiplier *= 0.5;
         })),
         ..default()
     },
@@ -1311,6 +1490,45 @@ generate_upgrade_list!(
         ..default()
     },
 
+    // Deadline
+
+    Crunch: Upgrade {
+        name: "Crunch".to_string(),
+        desc: "Skips a night of sleep. Adds VALUE hours to the jam deadline.".to_string(),
+        value: 8.0,
+        tech_debt: 2.0,
+        base_cost: 50.0,
+        cost_scale_factor: 1.2,
+        weight: 1.0,
+        remaining: 4,
+        upgrade_min: 5,
+        install: Some(world.register_system(|
+            mut clock: ResMut<JamClock>,
+            upgrade_list: Res<UpgradeList>,
+        | {
+            clock.extend_by_hours(upgrade_list[Crunch].value);
+        })),
+        ..default()
+    },
+
+    Extension: Upgrade {
+        name: "Extension".to_string(),
+        desc: "Begs the organizers for more time. Adds VALUE hours to the jam deadline.".to_string(),
+        value: 24.0,
+        tech_debt: 5.0,
+        base_cost: 500.0,
+        cost_scale_factor: 1.2,
+        weight: 0.5,
+        upgrade_min: 15,
+        install: Some(world.register_system(|
+            mut clock: ResMut<JamClock>,
+            upgrade_list: Res<UpgradeList>,
+        | {
+            clock.extend_by_hours(upgrade_list[Extension].value);
+        })),
+        ..default()
+    },
+
     // Specialization
 
     Specialization: Upgrade {
@@ -1448,11 +1666,14 @@ generate_upgrade_list!(
             mut simulation: ResMut<Simulation>,
             mut typer: ResMut<PassiveCodeTyper>,
             atlas_list: Res<AtlasList>,
+            mut rng: ResMut<RunRng>,
         | {
             typer.chars_per_entity += 1.0;
 
-            simulation.skin_set.replace_sprite_pack(&atlas_list, SpritePack::Text, &mut thread_rng());
-            events.send(SpritePackEvent);
+            if let Some(sprite_pack) = atlas_list.sprite_pack("text") {
+                simulation.replace_sprite_pack(&atlas_list, sprite_pack, &mut rng.skins);
+                events.send(SpritePackEvent);
+            }
         })),
         ..default()
     },
@@ -1,4 +1,6 @@
 mod despawn;
+#[cfg(feature = "web")]
+pub mod web;
 
 use bevy::prelude::*;
 use format_num::format_num;
@@ -54,3 +56,10 @@ pub fn gen_color(mut rng: impl Rng) -> Color {
         rng.gen_range(0.0..=1.0),
     )
 }
+
+/// Samples from the standard normal distribution using the Box-Muller transform.
+pub fn gen_normal(mut rng: impl Rng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
@@ -0,0 +1,30 @@
+//! Bindings to the browser APIs that replace `std::time` and `std::fs` on web.
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn date_now() -> f64;
+
+    #[wasm_bindgen(catch, js_namespace = localStorage, js_name = getItem)]
+    fn local_storage_get_item(key: &str) -> Result<Option<String>, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = localStorage, js_name = setItem)]
+    fn local_storage_set_item(key: &str, value: &str) -> Result<(), JsValue>;
+}
+
+/// Returns the current time in milliseconds since the Unix epoch.
+pub fn now_millis() -> f64 {
+    date_now()
+}
+
+/// Reads a value from the browser's local storage, or None if it's missing or unavailable.
+pub fn storage_get(key: &str) -> Option<String> {
+    local_storage_get_item(key).ok().flatten()
+}
+
+/// Writes a value to the browser's local storage.
+pub fn storage_set(key: &str, value: &str) -> Result<(), String> {
+    local_storage_set_item(key, value).map_err(|e| format!("{e:?}"))
+}